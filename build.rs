@@ -1,150 +1,990 @@
-use std::process::Command;
-use std::{env, path::PathBuf};
-use std::error::Error;
-use std::fmt;
-/// splits a command line by space and collects all arguments that start with `prefix`
-fn collect_args_with_prefix(cmd: &str, prefix: &str) -> Vec<String> {
-    shell_words::split(cmd)
-        .unwrap()
+use std::process::{self, Command};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::env;
+
+use openshmem_build::{Implementation, Library, Probe, ProbeError, wrapper_output};
+
+#[path = "build/bindings.rs"]
+mod bindings;
+
+/// removes repeated items, keeping the first occurrence of each
+fn dedup<T: PartialEq + Clone>(items: &[T]) -> Vec<T> {
+    let mut unique = Vec::with_capacity(items.len());
+    for item in items {
+        if !unique.contains(item) {
+            unique.push(item.clone());
+        }
+    }
+    unique
+}
+
+/// looks for `lib<name>.<ext>` with any of the given extensions in the search paths
+fn find_lib(name: &str, lib_paths: &[PathBuf], extensions: &[&str]) -> Option<PathBuf> {
+    lib_paths
+        .iter()
+        .flat_map(|dir| {
+            extensions
+                .iter()
+                .map(move |ext| dir.join(format!("lib{}.{}", name, ext)))
+        })
+        .find(|path| path.is_file())
+}
+
+/// looks for `lib<name>.a` in the given search paths
+fn find_static_lib(name: &str, lib_paths: &[PathBuf]) -> Option<PathBuf> {
+    find_lib(name, lib_paths, &["a"])
+}
+
+/// Directories the linker searches on its own, so libraries like `pthread` aren't reported missing
+const SYSTEM_LIB_DIRS: &[&str] = &[
+    "/lib",
+    "/lib64",
+    "/usr/lib",
+    "/usr/lib64",
+    "/usr/local/lib",
+    "/usr/local/lib64",
+];
+
+/// warns about every library that can't be found, which would otherwise only show up as a
+/// linker error much later
+fn warn_missing_libs(libs: &[String], lib_paths: &[PathBuf]) {
+    let target = env::var("TARGET").unwrap_or_default();
+    let mut dirs = lib_paths.to_vec();
+    dirs.extend(env::var_os("LIBRARY_PATH").iter().flat_map(env::split_paths));
+    for dir in SYSTEM_LIB_DIRS {
+        dirs.push(PathBuf::from(dir));
+        // Debian style multiarch directories, e.g. `/usr/lib/x86_64-linux-gnu`.
+        if let Some(arch) = target.split('-').next() {
+            dirs.push(Path::new(dir).join(format!("{}-linux-gnu", arch)));
+        }
+    }
+
+    for lib in libs {
+        if find_lib(lib, &dirs, &["so", "dylib", "a"]).is_none() {
+            println!(
+                "cargo:warning=library `{}` (lib{}.so/.dylib/.a) was not found in any search path, is the OpenSHMEM runtime installed and its module loaded?",
+                lib, lib
+            );
+        }
+    }
+}
+
+/// extra clang arguments from `OPENSHMEM_SYS_CLANG_ARGS`, split like a shell would
+fn clang_args_from_env() -> Vec<String> {
+    println!("cargo:rerun-if-env-changed=OPENSHMEM_SYS_CLANG_ARGS");
+
+    let Ok(args) = env::var("OPENSHMEM_SYS_CLANG_ARGS") else {
+        return Vec::new();
+    };
+    shell_words::split(&args).unwrap_or_else(|err| {
+        eprintln!("failed to split OPENSHMEM_SYS_CLANG_ARGS `{}`: {}", args, err);
+        process::exit(1);
+    })
+}
+
+/// C flags for the target the way the `cc` crate looks them up: the first of `CFLAGS_<target>`,
+/// `TARGET_CFLAGS` (`HOST_CFLAGS` when not cross-compiling) and `CFLAGS` that is set
+///
+/// Like `cc` this splits on whitespace only.
+fn cflags_from_env(target: &str, host: &str) -> Vec<String> {
+    let scope = if target == host { "HOST" } else { "TARGET" };
+    let vars = [
+        format!("CFLAGS_{}", target),
+        format!("CFLAGS_{}", target.replace('-', "_")),
+        format!("{}_CFLAGS", scope),
+        String::from("CFLAGS"),
+    ];
+    for var in &vars {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+
+    vars.iter()
+        .find_map(|var| env::var(var).ok())
+        .map(|flags| flags.split_whitespace().map(str::to_owned).collect())
+        .unwrap_or_default()
+}
+
+/// the C compiler for `target` from `CC_<target>` or `CC`, `cc` if neither is set
+fn cc_from_env(target: &str) -> String {
+    let vars = [
+        format!("CC_{}", target),
+        format!("CC_{}", target.replace('-', "_")),
+        String::from("CC"),
+    ];
+    for var in &vars {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+
+    vars.iter()
+        .find_map(|var| env::var(var).ok())
+        .unwrap_or_else(|| String::from("cc"))
+}
+
+/// comma separated bindgen patterns from the environment variable `var`
+fn patterns_from_env(var: &str) -> Vec<String> {
+    println!("cargo:rerun-if-env-changed={}", var);
+
+    env::var(var)
+        .map(|extra| {
+            extra
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(str::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// the header bindgen is run on, `OPENSHMEM_SYS_WRAPPER` if set
+fn wrapper_header_from_env() -> PathBuf {
+    println!("cargo:rerun-if-env-changed=OPENSHMEM_SYS_WRAPPER");
+
+    env::var_os("OPENSHMEM_SYS_WRAPPER")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("include/wrapper.h"))
+}
+
+/// the OpenSHMEM headers below `include_paths`, as far as they exist
+fn installed_headers(include_paths: &[PathBuf]) -> Vec<PathBuf> {
+    include_paths
         .iter()
-        .filter_map(|arg| {
-            if arg.starts_with(prefix) {
-                Some(arg[2..].to_owned())
+        .flat_map(|dir| [dir.to_path_buf(), dir.join("mpp")])
+        .flat_map(|dir| [dir.join("shmem.h"), dir.join("shmemx.h")])
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// the `shmemx.h` the `extensions` feature generates bindings for, exiting if there is none
+fn extensions_header(include_paths: &[PathBuf]) -> PathBuf {
+    installed_headers(include_paths)
+        .into_iter()
+        .find(|header| header.ends_with("shmemx.h"))
+        .unwrap_or_else(|| {
+            eprintln!(
+                "the extensions feature needs shmemx.h, but it is not in any of the include paths {:?}",
+                include_paths
+            );
+            process::exit(1);
+        })
+}
+
+/// the headers listed in `OPENSHMEM_SYS_EXTRA_HEADERS` (separated like `PATH`), to generate
+/// bindings for next to the wrapper header, exiting with a diagnostic if one can't be found
+///
+/// Relative names are looked up in the include paths, so `shcoll.h` works as well as
+/// `/opt/shcoll/include/shcoll.h`. The allowlist still applies, so headers declaring anything
+/// not named `shmem*` need `OPENSHMEM_SYS_ALLOWLIST_EXTRA` too.
+fn extra_headers_from_env(include_paths: &[PathBuf]) -> Vec<PathBuf> {
+    println!("cargo:rerun-if-env-changed=OPENSHMEM_SYS_EXTRA_HEADERS");
+    let Some(headers) = env::var_os("OPENSHMEM_SYS_EXTRA_HEADERS") else {
+        return Vec::new();
+    };
+
+    env::split_paths(&headers)
+        .filter(|header| !header.as_os_str().is_empty())
+        .map(|header| {
+            let found = if header.is_absolute() {
+                header.is_file().then(|| header.clone())
             } else {
-                None
-            }
+                include_paths
+                    .iter()
+                    .map(|dir| dir.join(&header))
+                    .find(|path| path.is_file())
+            };
+            found.unwrap_or_else(|| {
+                eprintln!(
+                    "OPENSHMEM_SYS_EXTRA_HEADERS lists {}, but it is not in any of the include paths {:?}",
+                    header.display(),
+                    include_paths
+                );
+                process::exit(1);
+            })
         })
         .collect()
 }
 
-#[derive(Debug, PartialEq)]
-struct UnquoteError {
-    quote: char,
+/// adds `libshmemx` to the link line for the `extensions` feature, if the installation keeps the
+/// extensions in a separate library
+fn link_extensions(oshmem: &mut Library) {
+    if !oshmem.libs.iter().any(|lib| lib == "shmemx")
+        && find_lib("shmemx", &oshmem.lib_paths, &["so", "dylib", "a"]).is_some()
+    {
+        oshmem.libs.push(String::from("shmemx"));
+    }
 }
 
+/// swaps every library that has a `lib<name>_prof` variant in the search paths for it, for the
+/// `profiling-lib` feature
+///
+/// Wrappers of an instrumented install may already link the `_prof` libraries, those are kept.
+/// If there is no variant at all the regular libraries are linked, with a warning.
+fn link_profiling_libs(oshmem: &mut Library) {
+    let mut found = oshmem.libs.iter().any(|lib| lib.ends_with("_prof"));
+    for lib in &mut oshmem.libs {
+        let prof = format!("{}_prof", lib);
+        if lib.ends_with("_prof")
+            || find_lib(&prof, &oshmem.lib_paths, &["so", "dylib", "a"]).is_none()
+        {
+            continue;
+        }
+        if let Some(static_lib) = oshmem.static_libs.iter_mut().find(|name| *name == lib) {
+            *static_lib = prof.clone();
+        }
+        *lib = prof;
+        found = true;
+    }
+    if !found {
+        println!(
+            "cargo:warning=profiling-lib is enabled but none of {} has a `_prof` variant in the search paths, linking the regular libraries",
+            oshmem.libs.join(", ")
+        );
+    }
+}
+
+/// generates the bindings into `out_file`, reusing an earlier result if nothing changed
+///
+/// The cache key covers the contents of `headers` and every flag the builder would hand to
+/// bindgen, which includes the resolved include paths and defines.
+fn generate_cached(builder: bindgen::Builder, headers: &[PathBuf], out_file: &Path) {
+    let mut hasher = DefaultHasher::new();
+    for header in headers {
+        std::fs::read(header)
+            .expect("Couldn't read header")
+            .hash(&mut hasher);
+    }
+    builder.command_line_flags().hash(&mut hasher);
+    let cached = out_file.with_file_name(format!("bindings-{:016x}.rs", hasher.finish()));
 
-impl UnquoteError {
-    fn new(quote: char) -> UnquoteError {
-        UnquoteError { quote }
+    if !cached.is_file() {
+        builder
+            // Finish the builder and generate the bindings.
+            .generate()
+            // Unwrap the Result and panic on failure.
+            .expect("Unable to generate bindings")
+            .write_to_file(&cached)
+            .expect("Couldn't write bindings!");
     }
+    std::fs::copy(&cached, out_file).expect("Couldn't write bindings!");
 }
 
-impl fmt::Display for UnquoteError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Quotes '{}' not closed.", self.quote)
+/// routines returning a status code that the `must-use` feature marks `#[must_use]`
+const MUST_USE_FUNCTIONS: &[&str] = &[
+    "shmem_init_thread",
+    "shmem_ctx_create",
+    "shmem_team_create_ctx",
+    "shmem_team_split_strided",
+    "shmem_team_split_2d",
+    "shmem_team_sync",
+    "shmem_broadcastmem",
+    "shmem_collectmem",
+    "shmem_fcollectmem",
+    "shmem_alltoallmem",
+    "shmem_alltoallsmem",
+];
+
+/// endings of the typed team collectives, which return a status code as well, e.g.
+/// `shmem_int_alltoall` or `shmem_int32_sum_reduce`
+const MUST_USE_SUFFIXES: &[&str] = &[
+    "_broadcast",
+    "_collect",
+    "_fcollect",
+    "_alltoall",
+    "_alltoalls",
+    "_reduce",
+];
+
+/// puts `#[must_use]` on the declarations of [`MUST_USE_FUNCTIONS`] and the routines ending in
+/// [`MUST_USE_SUFFIXES`] in the generated `bindings`
+///
+/// bindgen can only attach attributes to types, so this edits its output.
+fn add_must_use(bindings: &Path) {
+    let code = std::fs::read_to_string(bindings).expect("Couldn't read bindings!");
+    let mut out = String::with_capacity(code.len());
+    for line in code.lines() {
+        let name = line
+            .trim_start()
+            .strip_prefix("pub fn ")
+            .and_then(|rest| rest.split('(').next())
+            .filter(|name| name.starts_with("shmem_"));
+        if let Some(name) = name
+            && (MUST_USE_FUNCTIONS.contains(&name)
+                || MUST_USE_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)))
+        {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            out.push_str(indent);
+            out.push_str("#[must_use]\n");
+        }
+        out.push_str(line);
+        out.push('\n');
     }
+    std::fs::write(bindings, out).expect("Couldn't write bindings!");
 }
 
-impl Error for UnquoteError {}
+/// the feature combinations the build script supports, printed with every conflict
+const FEATURE_MATRIX: &str = "\
+supported feature combinations:
+  - at most one of impl-openmpi, impl-sandia and impl-osss
+  - vendored-bindings skips bindgen and the build time checks, so extensions, profiling,
+    complex, newtype-enums, opaque-handles, must-use, no-layout-tests,
+    assert-thread-multiple and reproducible have no effect with it
+  - vendored-bindings with static needs OPENSHMEM_LIB_DIR pointing at the archives";
+
+/// whether the cargo feature `name` is enabled
+fn feature_enabled(name: &str) -> bool {
+    let var = format!("CARGO_FEATURE_{}", name.to_uppercase().replace('-', "_"));
+    env::var_os(var).is_some()
+}
 
-fn unquote(s: &str) -> Result<String, UnquoteError> {
-    if s.chars().count() < 2 {
-        return Ok(String::from(s));
+/// checks the enabled features against [`FEATURE_MATRIX`] before anything is probed
+///
+/// Combinations that can't build stop it with the conflict and the fix, features that would
+/// be silently ignored get a warning.
+fn check_features() {
+    if feature_enabled("vendored-bindings") {
+        let ignored: Vec<_> = [
+            "extensions",
+            "profiling",
+            "complex",
+            "newtype-enums",
+            "opaque-handles",
+            "must-use",
+            "no-layout-tests",
+            "assert-thread-multiple",
+            "reproducible",
+        ]
+        .into_iter()
+        .filter(|feature| feature_enabled(feature))
+        .collect();
+        if !ignored.is_empty() {
+            println!(
+                "cargo:warning=vendored-bindings uses the checked-in bindings, ignoring the features {}",
+                ignored.join(", ")
+            );
+        }
+
+        println!("cargo:rerun-if-env-changed=OPENSHMEM_LIB_DIR");
+        if feature_enabled("static") && env::var_os("OPENSHMEM_LIB_DIR").is_none() {
+            eprintln!(
+                "the features vendored-bindings and static need OPENSHMEM_LIB_DIR, there is no probe to find the archives"
+            );
+            eprintln!("{}", FEATURE_MATRIX);
+            process::exit(1);
+        }
     }
+}
 
-    let quote = s.chars().next().unwrap();
+/// the implementation picked by the `impl-*` features, if any
+///
+/// The features are mutually exclusive, enabling two of them stops the build.
+fn implementation_from_features() -> Option<Implementation> {
+    let selected: Vec<_> = [
+        ("impl-openmpi", Implementation::OpenMpi),
+        ("impl-sandia", Implementation::Sandia),
+        ("impl-osss", Implementation::Osss),
+    ]
+    .into_iter()
+    .filter(|(feature, _)| feature_enabled(feature))
+    .collect();
 
-    if quote != '"' && quote != '\'' && quote != '`' {
-        return Ok(String::from(s));
+    match selected.as_slice() {
+        [] => None,
+        [(_, implementation)] => Some(*implementation),
+        _ => {
+            let features: Vec<_> = selected.iter().map(|(feature, _)| *feature).collect();
+            eprintln!(
+                "the features {} are mutually exclusive, enable only one OpenSHMEM implementation",
+                features.join(", ")
+            );
+            eprintln!("{}", FEATURE_MATRIX);
+            process::exit(1);
+        }
     }
+}
 
-    if s.chars().last().unwrap() != quote {
-        return Err(UnquoteError::new(quote));
+/// probes for the OpenSHMEM installation, exiting with a diagnostic if none can be found
+fn probe(requested: Option<Implementation>) -> Library {
+    let mut probe = Probe::new();
+    if let Some(implementation) = requested {
+        probe.implementation(implementation);
     }
+    probe.probe().unwrap_or_else(|err| {
+        eprintln!("failed to locate OpenSHMEM: {}", err);
+        if let ProbeError::NotFound { .. } = err {
+            eprintln!("hint: load your OpenSHMEM module or set OSHMEM_CC");
+        }
+        process::exit(1);
+    })
+}
+
+/// runs bindgen over the wrapper header using the probed include paths and defines
+fn generate_bindings(oshmem: &Library, target: &str, host: &str, out_path: &Path) {
+    let header = wrapper_header_from_env();
 
-    let s = &s[1..s.len() - 1];
-    Ok(String::from(s))
+    let mut builder = bindings::builder(oshmem, &header)
+        // Check the size, alignment and field offsets of every generated struct against what
+        // clang computed. bindgen emits these as const assertions, so a mismatch fails the build
+        // itself rather than only `cargo test`. `no-layout-tests` skips them to save build time.
+        .layout_tests(!feature_enabled("no-layout-tests"));
+    println!("cargo:rerun-if-env-changed=OPENSHMEM_SYS_FORTRAN_INTEROP");
+    if env::var_os("OPENSHMEM_SYS_FORTRAN_INTEROP").is_none() {
+        // Combined C/Fortran headers declare the Fortran entry points (`shmem_*_f`) and the
+        // handle conversions that take an `MPI_Fint`, neither of which C callers use. Patterns
+        // are anchored, so this only matches names ending in the suffixes.
+        builder = builder
+            .blocklist_function(".*_f|.*_f2c|.*_c2f")
+            .blocklist_type("MPI_Fint");
+    }
+    if feature_enabled("extensions") {
+        builder = builder
+            .header(extensions_header(&oshmem.include_paths).to_string_lossy())
+            .allowlist_function("shmemx_.*")
+            .allowlist_type("shmemx.*")
+            .allowlist_var("SHMEMX.*");
+    }
+    if feature_enabled("complex") {
+        // Headers only declare the `shmem_complex{f,d}_*` reductions when complex support is
+        // there, so `<complex.h>` has to be seen first. The `shmem_.*` allowlist already covers
+        // the routines, bindgen maps `double _Complex` to its `__BindgenComplex<f64>`.
+        builder = builder.clang_args(["-include", "complex.h"]);
+    }
+    if feature_enabled("newtype-enums") {
+        // Only named C enums can become newtypes, implementations that use macros (like Sandia)
+        // keep plain constants either way.
+        builder = builder.newtype_enum("shmem_.*(cmp|signal).*");
+    }
+    if feature_enabled("opaque-handles") {
+        // The handles are only ever passed back to the library, hide whatever the header
+        // exposes of them. `shmem_team_config_t` is filled in by the caller and stays as it is.
+        builder = builder.opaque_type("shmem_team_t|shmem_ctx_t");
+    }
+    if feature_enabled("profiling") {
+        // The profiling interface lives in the same library, so linking is unaffected.
+        builder = builder.allowlist_function("pshmem_.*");
+    }
+    if target != host {
+        // Make clang lay out `size_t`, pointers etc. for the target rather than the host.
+        builder = builder.clang_arg(format!("--target={}", target));
+    }
+    builder = builder.clang_args(cflags_from_env(target, host));
+    // Last, so site specific flags can override anything above.
+    builder = builder.clang_args(clang_args_from_env());
+    for pattern in patterns_from_env("OPENSHMEM_SYS_ALLOWLIST_EXTRA") {
+        builder = builder
+            .allowlist_function(&pattern)
+            .allowlist_type(&pattern)
+            .allowlist_var(&pattern);
+    }
+    for pattern in patterns_from_env("OPENSHMEM_SYS_BLOCKLIST_EXTRA") {
+        builder = builder
+            .blocklist_function(&pattern)
+            .blocklist_type(&pattern)
+            .blocklist_var(&pattern);
+    }
+    let extra_headers = extra_headers_from_env(&oshmem.include_paths);
+    for extra in &extra_headers {
+        builder = builder.header(extra.to_string_lossy());
+    }
+    // The installed headers count too, so an in-place upgrade of the library regenerates.
+    let mut headers = vec![header];
+    headers.extend(extra_headers);
+    headers.extend(installed_headers(&oshmem.include_paths));
+    generate_cached(builder, &headers, &out_path.join("bindings.rs"));
+    if feature_enabled("must-use") {
+        add_must_use(&out_path.join("bindings.rs"));
+    }
+    if feature_enabled("reproducible") {
+        let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        bindings::strip_bindings_paths(&out_path.join("bindings.rs"), out_path, &manifest_dir);
+    }
+
+    // let cargo knows if any of the headers is changed
+    for header in &headers {
+        println!("cargo:rerun-if-changed={}", header.to_string_lossy());
+    }
+}
+
+/// tells cargo how to link against `oshmem` and exports its metadata to dependents
+///
+/// Build scripts of crates depending on this one can read
+/// - `DEP_OPENSHMEM_INCLUDE`: header search paths, joined like `PATH`
+/// - `DEP_OPENSHMEM_LIB_PATHS`: library search paths, joined like `PATH`
+/// - `DEP_OPENSHMEM_LIBS`: colon separated library names
+/// - `DEP_OPENSHMEM_VERSION`: the OpenSHMEM spec version or `unknown`
+/// - `DEP_OPENSHMEM_IMPLEMENTATION`: `openmpi`, `sandia`, `osss`, `cray` or `unknown`
+fn emit_link_metadata(oshmem: &Library) {
+    // `oshcc -show` tends to repeat the same `-L` several times.
+    let lib_paths = dedup(&oshmem.lib_paths);
+    for path in &lib_paths {
+        println!("cargo:rustc-link-search=native={}", path.to_string_lossy());
+    }
+    warn_missing_libs(&oshmem.libs, &lib_paths);
+    let link_static = feature_enabled("static");
+    // Search paths apply to the whole link line, but the libraries keep the order the wrapper
+    // gave them in.
+    for lib in &oshmem.libs {
+        if oshmem.static_libs.contains(lib) {
+            println!("cargo:rustc-link-lib=static={}", lib);
+        } else if !link_static {
+            println!("cargo:rustc-link-lib={}", lib);
+        } else if find_static_lib(lib, &lib_paths).is_some() {
+            println!("cargo:rustc-link-lib=static={}", lib);
+        } else {
+            // System libraries like `m` or `pthread` often don't ship an archive.
+            println!("cargo:warning=no lib{}.a found, linking `{}` dynamically", lib, lib);
+            println!("cargo:rustc-link-lib={}", lib);
+        }
+    }
+    for path in dedup(&oshmem.framework_paths) {
+        println!("cargo:rustc-link-search=framework={}", path.to_string_lossy());
+    }
+    for framework in &oshmem.frameworks {
+        println!("cargo:rustc-link-lib=framework={}", framework);
+    }
+    for arg in &oshmem.link_args {
+        println!("cargo:rustc-link-arg={}", arg);
+    }
+    // Lets the tests and examples of this crate find the runtime without LD_LIBRARY_PATH. Cargo
+    // doesn't pass link args on to dependents, their binaries still need their own rpath.
+    println!("cargo:rerun-if-env-changed=OPENSHMEM_SYS_NO_RPATH");
+    if env::var_os("OPENSHMEM_SYS_NO_RPATH").is_none() {
+        for path in &lib_paths {
+            println!("cargo:rustc-link-arg=-Wl,-rpath,{}", path.to_string_lossy());
+        }
+    }
+
+    let join = |paths: &[PathBuf]| {
+        env::join_paths(paths)
+            .expect("search path contains the path separator")
+            .to_string_lossy()
+            .into_owned()
+    };
+    println!("cargo:include={}", join(&oshmem.include_paths));
+    println!("cargo:lib_paths={}", join(&lib_paths));
+    println!("cargo:libs={}", oshmem.libs.join(":"));
+    println!("cargo:version={}", oshmem.version);
+    println!("cargo:implementation={}", oshmem.implementation.name());
 }
 
-fn probe_via_oshcc(oshcc: &str) -> std::io::Result<Library> {
-    // Capture the output of `mpicc -show`. This usually gives the actual compiler command line
-    // invoked by the `mpicc` compiler wrapper.
-    Command::new(oshcc).arg("-show").output().map(|cmd| {
-        let output = String::from_utf8(cmd.stdout).expect("mpicc output is not valid UTF-8");
-        // Collect the libraries that an MPI C program should be linked to...
-        let libs = collect_args_with_prefix(output.as_ref(), "-l");
-        // ... and the library search directories...
-        let libdirs = collect_args_with_prefix(output.as_ref(), "-L")
-            .into_iter()
-            .filter_map(|x| unquote(&x).ok())
-            .map(PathBuf::from)
-            .collect();
-        // ... and the header search directories.
-        let headerdirs = collect_args_with_prefix(output.as_ref(), "-I")
-            .into_iter()
-            .filter_map(|x| unquote(&x).ok())
-            .map(PathBuf::from)
-            .collect();
+/// spec versions that gate parts of the safe wrappers, as `shmem_<major>_<minor>` cfgs
+const GATED_VERSIONS: &[(u32, u32)] = &[(1, 4), (1, 5)];
+
+/// sets the `shmem_<major>_<minor>` cfg of every gated spec version the library implements
+///
+/// An unknown version enables all of them, the bindings then decide what compiles.
+fn emit_version_cfgs(oshmem: &Library) {
+    let detected = oshmem.spec_version();
+    for &(major, minor) in GATED_VERSIONS {
+        println!("cargo:rustc-check-cfg=cfg(shmem_{}_{})", major, minor);
+        if detected.is_none_or(|detected| detected >= (major, minor)) {
+            println!("cargo:rustc-cfg=shmem_{}_{}", major, minor);
+        }
+    }
 
-            Library {
-                oshcc: Some(oshcc.to_string()),
-                libs,
-                lib_paths: libdirs,
-                include_paths: headerdirs,
-                version: String::from("unknown"),
-                _priv: (),
+    if feature_enabled("shmem-1-5")
+        && let Some(detected) = detected
+        && detected < (1, 5)
+    {
+        println!(
+            "cargo:warning=the shmem-1-5 feature is enabled but the installed OpenSHMEM implements spec {}, leaving out the signaling wrappers",
+            oshmem.version
+        );
+    }
+}
+
+/// transports recognized on the link line, as the `shmem_transport_<name>` cfgs and the libraries
+/// that give them away
+const TRANSPORTS: &[(&str, &[&str])] = &[
+    ("ucx", &["ucp", "uct", "ucs"]),
+    ("libfabric", &["fabric"]),
+    ("verbs", &["ibverbs"]),
+];
+
+/// sets `shmem_transport_<name>` for every transport whose libraries the link line names and
+/// exports the names as `cargo:transports`
+///
+/// This only sees what the wrapper links explicitly. Most installs load the transport at run
+/// time, so a missing cfg doesn't mean the transport isn't used, nor does a present one mean it
+/// is the one selected.
+fn emit_transport_cfgs(oshmem: &Library) {
+    let names: Vec<_> = TRANSPORTS
+        .iter()
+        .map(|(name, _)| format!("shmem_transport_{}", name))
+        .collect();
+    println!("cargo:rustc-check-cfg=cfg({})", names.join(", "));
+
+    let mut found = Vec::new();
+    for &(name, libs) in TRANSPORTS {
+        if oshmem.libs.iter().any(|lib| libs.contains(&lib.as_str())) {
+            println!("cargo:rustc-cfg=shmem_transport_{}", name);
+            found.push(name);
+        }
+    }
+    println!("cargo:transports={}", found.join(":"));
+}
+
+/// panics if bindgen found none of the OpenSHMEM routines
+///
+/// An include that doesn't resolve still leaves bindgen with a valid, empty header, and the
+/// mistake would otherwise only show up as undefined symbols in the crates using the bindings.
+fn check_bindings_not_empty(bindings: &str, oshmem: &Library) {
+    if !bindings.contains("pub fn shmem_") {
+        panic!(
+            "no SHMEM symbols found in the generated bindings, check the include paths (searched {:?}, header {})",
+            oshmem.include_paths,
+            wrapper_header_from_env().display()
+        );
+    }
+}
+
+/// sets the cfg named after the wall clock routine the bindings declare, if any
+///
+/// Open MPI has `shmem_wtime` in `shmem.h`, the others keep `shmemx_wtime` in `shmemx.h`, so
+/// the routine native to the implementation is preferred when both are there.
+fn emit_timer_cfg(bindings: &str, implementation: Implementation) {
+    println!("cargo:rustc-check-cfg=cfg(shmem_wtime, shmemx_wtime)");
+
+    let candidates = match implementation {
+        Implementation::OpenMpi => ["shmem_wtime", "shmemx_wtime"],
+        _ => ["shmemx_wtime", "shmem_wtime"],
+    };
+    if let Some(timer) = candidates
+        .into_iter()
+        .find(|timer| bindings.contains(&format!("pub fn {}()", timer)))
+    {
+        println!("cargo:rustc-cfg={}", timer);
+    }
+}
+
+/// compiles `code` as `OUT_DIR/<name>.c` against the installation and runs it, returning what
+/// it printed
+///
+/// `None` if the program can't be built or run, the callers only use it for diagnostics.
+fn run_check_program(
+    oshmem: &Library,
+    name: &str,
+    code: &str,
+    target: &str,
+    out_path: &Path,
+) -> Option<String> {
+    let source = out_path.join(format!("{}.c", name));
+    let program = out_path.join(name);
+    std::fs::write(&source, code).ok()?;
+
+    // The wrapper knows the right flags, a plain compiler needs them spelled out.
+    let mut compile = match &oshmem.oshcc {
+        Some(oshcc) => Command::new(oshcc),
+        None => {
+            let mut cc = Command::new(cc_from_env(target));
+            cc.args(oshmem.include_paths.iter().map(|dir| format!("-I{}", dir.display())))
+                .args(oshmem.defines.iter().map(|def| format!("-D{}", def)));
+            cc
+        }
+    };
+    compile.arg(&source).arg("-o").arg(&program);
+    if oshmem.oshcc.is_none() {
+        compile
+            .args(oshmem.lib_paths.iter().map(|dir| format!("-L{}", dir.display())))
+            .args(oshmem.libs.iter().map(|lib| format!("-l{}", lib)))
+            .args(&oshmem.link_args);
+    }
+    // On module based sites the wrapper, and the program it builds, only work in the environment
+    // the site's script sets up.
+    let env_script = oshmem.env_script.as_deref();
+    if !wrapper_output(&mut compile, env_script).is_ok_and(|out| out.status.success()) {
+        return None;
+    }
+
+    let library_path = env::join_paths(
+        oshmem
+            .lib_paths
+            .iter()
+            .cloned()
+            .chain(env::var_os("LD_LIBRARY_PATH").iter().flat_map(env::split_paths)),
+    )
+    .unwrap_or_default();
+    let out = wrapper_output(
+        Command::new(&program).env("LD_LIBRARY_PATH", library_path),
+        env_script,
+    )
+    .ok()?;
+    Some(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// compiles and runs a program printing the version the headers declare and the one the library
+/// reports, warning if they differ
+///
+/// A mismatch means the headers and the library come from different installs. This is only a
+/// diagnostic, nothing is reported if the program can't be built or run.
+fn check_library_version(oshmem: &Library, header: &Path, target: &str, out_path: &Path) {
+    let Ok(header) = header.canonicalize() else {
+        return;
+    };
+    let code = format!(
+        r#"#include "{}"
+#include <stdio.h>
+
+int main(void) {{
+    int major, minor;
+    shmem_info_get_version(&major, &minor);
+    printf("%d.%d %d.%d\n", SHMEM_MAJOR_VERSION, SHMEM_MINOR_VERSION, major, minor);
+    return 0;
+}}
+"#,
+        header.display()
+    );
+    let Some(out) = run_check_program(oshmem, "version_check", &code, target, out_path) else {
+        return;
+    };
+    if let Some((headers, library)) = out.trim().split_once(' ')
+        && headers != library
+    {
+        println!(
+            "cargo:warning=the OpenSHMEM headers declare version {} but the library reports {}, are modules of two different installs loaded?",
+            headers, library
+        );
+    }
+}
+
+/// runs a program asking for `SHMEM_THREAD_MULTIPLE`, warning unless the library grants it
+///
+/// This is the `assert-thread-multiple` feature. Unlike the version check it also warns when
+/// the program can't be built or run, since it was asked for explicitly; some installs can't
+/// start a program without `oshrun`.
+fn check_thread_multiple(oshmem: &Library, header: &Path, target: &str, out_path: &Path) {
+    let code = header.canonicalize().ok().map(|header| {
+        format!(
+            r#"#include "{}"
+#include <stdio.h>
+
+int main(void) {{
+    int provided;
+    if (shmem_init_thread(SHMEM_THREAD_MULTIPLE, &provided) != 0) {{
+        printf("failed\n");
+        return 0;
+    }}
+    printf("%d %d\n", provided, SHMEM_THREAD_MULTIPLE);
+    shmem_finalize();
+    return 0;
+}}
+"#,
+            header.display()
+        )
+    });
+    let out =
+        code.and_then(|code| run_check_program(oshmem, "thread_check", &code, target, out_path));
+
+    match out.as_deref().map(str::trim) {
+        None => println!(
+            "cargo:warning=assert-thread-multiple: couldn't build or run the thread level check, SHMEM_THREAD_MULTIPLE support is unknown"
+        ),
+        Some("failed") => println!(
+            "cargo:warning=assert-thread-multiple: shmem_init_thread(SHMEM_THREAD_MULTIPLE) failed, the installation can't provide SHMEM_THREAD_MULTIPLE"
+        ),
+        Some(out) => {
+            if let Some((provided, multiple)) = out.split_once(' ')
+                && provided != multiple
+            {
+                println!(
+                    "cargo:warning=assert-thread-multiple: the installation only provides thread level {} instead of SHMEM_THREAD_MULTIPLE ({}), was it built without thread support?",
+                    provided, multiple
+                );
             }
+        }
+    }
+}
+
+/// The constants the `limits` module guarantees, with the Rust type they are exported as
+const LIMITS: &[(&str, &str)] = &[
+    ("SHMEM_MAX_NAME_LEN", "usize"),
+    ("SHMEM_MAJOR_VERSION", "c_int"),
+    ("SHMEM_MINOR_VERSION", "c_int"),
+];
+
+/// the value bindgen generated for the integer constant `name`, if it kept the macro
+fn constant_from_bindings(bindings: &str, name: &str) -> Option<u64> {
+    let prefix = format!("pub const {}:", name);
+    bindings.lines().find_map(|line| {
+        let (_, value) = line.trim().strip_prefix(&prefix)?.split_once('=')?;
+        value.trim().trim_end_matches(';').trim().parse().ok()
     })
 }
 
-/// Result of a successfull probe
-#[allow(clippy::manual_non_exhaustive)]
-#[derive(Clone, Debug)]
-pub struct Library {
-    /// Path to compiler capable of building MPI programs
-    pub oshcc: Option<String>,
-    /// Names of the native MPI libraries that need to be linked
-    pub libs: Vec<String>,
-    /// Search path for native MPI libraries
-    pub lib_paths: Vec<PathBuf>,
-    /// Search path for C header files
-    pub include_paths: Vec<PathBuf>,
-    /// The version of the MPI library
-    pub version: String,
-    _priv: (),
+/// compiles and runs a stub printing each of `LIMITS` as the headers define it
+///
+/// This sees through macros bindgen drops, like ones with casts or defined in terms of other
+/// macros.
+fn limits_from_stub(
+    oshmem: &Library,
+    header: &Path,
+    target: &str,
+    out_path: &Path,
+) -> Option<Vec<u64>> {
+    let header = header.canonicalize().ok()?;
+    let prints: String = LIMITS
+        .iter()
+        .map(|(name, _)| format!("    printf(\"%llu\\n\", (unsigned long long)({}));\n", name))
+        .collect();
+    let code = format!(
+        r#"#include "{}"
+#include <stdio.h>
+
+int main(void) {{
+{}    return 0;
+}}
+"#,
+        header.display(),
+        prints
+    );
+    let out = run_check_program(oshmem, "limits", &code, target, out_path)?;
+    let values: Vec<u64> = out
+        .lines()
+        .map(|line| line.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    (values.len() == LIMITS.len()).then_some(values)
 }
 
+/// writes `OUT_DIR/limits.rs` defining every constant in `LIMITS`
+///
+/// The values come from `stub` if it could run, else from the `bindings`, else the version from
+/// the probe and the 256 bytes every known implementation uses for `SHMEM_MAX_NAME_LEN`.
+fn write_limits(oshmem: &Library, bindings: &str, stub: Option<Vec<u64>>, out_path: &Path) {
+    let version = oshmem.spec_version();
+    let mut code = String::new();
+    for (i, (name, ty)) in LIMITS.iter().enumerate() {
+        let fallback = match *name {
+            "SHMEM_MAJOR_VERSION" => version.map(|(major, _)| major.into()),
+            "SHMEM_MINOR_VERSION" => version.map(|(_, minor)| minor.into()),
+            _ => None,
+        };
+        let value = stub
+            .as_ref()
+            .map(|values| values[i])
+            .or_else(|| constant_from_bindings(bindings, name))
+            .or(fallback)
+            .unwrap_or_else(|| {
+                let value = if *name == "SHMEM_MAX_NAME_LEN" { 256 } else { 0 };
+                println!("cargo:warning=can't determine {}, assuming {}", name, value);
+                value
+            });
+        code.push_str(&format!(
+            "/// `{}` as the installed headers define it\npub const {}: {} = {};\n",
+            name, name, ty, value
+        ));
+    }
+    std::fs::write(out_path.join("limits.rs"), code).expect("Couldn't write limits!");
+}
 
-fn main() {
-    let oshmem = probe_via_oshcc("oshcc").unwrap();
-
-    // The bindgen::Builder is the main entry point
-    // to bindgen, and lets you build up options for
-    // the resulting bindings.
-    let bindings = bindgen::Builder::default()
-        // The input header we would like to generate
-        // bindings for.
-        .clang_args(
-            oshmem
-                .include_paths
-                .iter()
-                .map(|p| format!("-I{}", p.to_string_lossy())),
-        )
-        .header("include/wrapper.h")
-        // Tell cargo to invalidate the built ucx_sys whenever any of the
-        // included header files changed.
-        .prepend_enum_name(false)
-        // Finish the builder and generate the bindings.
-        .generate()
-        // Unwrap the Result and panic on failure.
-        .expect("Unable to generate bindings");
+/// builds a `Library` from `OPENSHMEM_LIB_DIR` and `OPENSHMEM_LIBS` for the prebuilt bindings
+///
+/// Without `OPENSHMEM_LIBS` this links the library `requested` ships, `oshmem` by default.
+fn library_from_env(requested: Option<Implementation>) -> Library {
+    println!("cargo:rerun-if-env-changed=OPENSHMEM_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=OPENSHMEM_LIBS");
 
-    // let cargo knows if wrapper.h is changed
-    println!("cargo:rerun-if-changed=include/wrapper.h");
+    let lib_paths = env::var_os("OPENSHMEM_LIB_DIR")
+        .map(|dirs| env::split_paths(&dirs).collect())
+        .unwrap_or_default();
+    let libs = env::var("OPENSHMEM_LIBS")
+        .map(|libs| {
+            libs.split(':')
+                .filter(|lib| !lib.is_empty())
+                .map(str::to_owned)
+                .collect()
+        })
+        .unwrap_or_else(|_| {
+            let lib = match requested {
+                Some(Implementation::Sandia) => "sma",
+                Some(Implementation::Osss) => "shmem",
+                _ => "oshmem",
+            };
+            vec![String::from(lib)]
+        });
 
-    for path in oshmem.lib_paths {
-        println!("cargo:rustc-link-search={}", path.to_string_lossy());
+    let mut oshmem = Library::new(libs, lib_paths);
+    if let Some(implementation) = requested {
+        oshmem.implementation = implementation;
     }
-    for path in oshmem.include_paths {
-        println!("cargo:include={}", path.to_string_lossy());
+    oshmem
+}
+
+/// reads the site configuration file named by `OPENSHMEM_SYS_CONFIG`, if set, exiting with a
+/// diagnostic if it can't be parsed
+///
+/// The file replaces probing altogether, see `Library::from_site_config` for its format.
+fn library_from_config(requested: Option<Implementation>) -> Option<Library> {
+    println!("cargo:rerun-if-env-changed=OPENSHMEM_SYS_CONFIG");
+    let path = PathBuf::from(env::var_os("OPENSHMEM_SYS_CONFIG")?);
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let mut oshmem = Library::from_site_config(&path).unwrap_or_else(|err| {
+        eprintln!("failed to read OPENSHMEM_SYS_CONFIG: {}", err);
+        process::exit(1);
+    });
+    if let Some(implementation) = requested {
+        oshmem.implementation = implementation;
     }
-    for lib in oshmem.libs {
-        println!("cargo:rustc-link-lib={}", lib);
+    Some(oshmem)
+}
+
+fn main() {
+    check_features();
+    let requested = implementation_from_features();
+    let site_config = library_from_config(requested);
+
+    // The checked-in bindings need neither the wrapper nor clang, only the link information.
+    if feature_enabled("vendored-bindings") {
+        let mut oshmem = site_config.unwrap_or_else(|| library_from_env(requested));
+        if feature_enabled("profiling-lib") {
+            link_profiling_libs(&mut oshmem);
+        }
+        emit_link_metadata(&oshmem);
+        emit_version_cfgs(&oshmem);
+        emit_transport_cfgs(&oshmem);
+        let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        println!(
+            "cargo:bindings={}",
+            manifest_dir.join("src/bindings_prebuilt.rs").display()
+        );
+        // The prebuilt bindings have no timer.
+        emit_timer_cfg("", oshmem.implementation);
+        let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+        let prebuilt = std::fs::read_to_string(manifest_dir.join("src/bindings_prebuilt.rs"))
+            .expect("Couldn't read bindings!");
+        write_limits(&oshmem, &prebuilt, None, &out_path);
+        return;
     }
 
-    // Write the bindings to the $OUT_DIR/bindings.rs file.
+    let target = env::var("TARGET").unwrap();
+    let host = env::var("HOST").unwrap();
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
-    bindings
-        .write_to_file(out_path.join("bindings.rs"))
-        .expect("Couldn't write bindings!");
+
+    let mut oshmem = site_config.unwrap_or_else(|| probe(requested));
+    if feature_enabled("extensions") {
+        link_extensions(&mut oshmem);
+    }
+    if feature_enabled("profiling-lib") {
+        link_profiling_libs(&mut oshmem);
+    }
+    generate_bindings(&oshmem, &target, &host, &out_path);
+    emit_link_metadata(&oshmem);
+    emit_version_cfgs(&oshmem);
+    emit_transport_cfgs(&oshmem);
+    let bindings_path = out_path.join("bindings.rs");
+    println!("cargo:bindings={}", bindings_path.display());
+    let bindings = std::fs::read_to_string(&bindings_path).expect("Couldn't read bindings!");
+    check_bindings_not_empty(&bindings, &oshmem);
+    emit_timer_cfg(&bindings, oshmem.implementation);
+    // The check programs would have to run on the target.
+    let stub = (target == host)
+        .then(|| limits_from_stub(&oshmem, &wrapper_header_from_env(), &target, &out_path))
+        .flatten();
+    write_limits(&oshmem, &bindings, stub, &out_path);
+    if target == host {
+        check_library_version(&oshmem, &wrapper_header_from_env(), &target, &out_path);
+        if feature_enabled("assert-thread-multiple") {
+            check_thread_multiple(&oshmem, &wrapper_header_from_env(), &target, &out_path);
+        }
+    }
 }
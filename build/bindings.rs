@@ -0,0 +1,110 @@
+//! The bindgen setup of the build script that doesn't depend on features or the environment.
+//!
+//! `build.rs` and `openshmem-build/tests/reproducible.rs` both include this file, so the test
+//! generates the bindings the way a build does.
+
+use std::path::Path;
+
+use openshmem_build::{Library, strip_build_paths};
+
+/// Integer typedefs replaced by the Rust primitive they stand for, so lengths and offsets from
+/// slices can be passed without casts
+///
+/// bindgen has no switch for `ptrdiff_t` like it has for `size_t`, and some implementations
+/// declare the API in terms of their own `shmem_size_t`/`shmem_ptrdiff_t`, which would otherwise
+/// come out as separate aliases of whatever the platform's `size_t` is.
+const INTEGER_ALIASES: &[(&str, &str)] = &[
+    ("ptrdiff_t", "isize"),
+    ("shmem_size_t", "usize"),
+    ("shmem_ptrdiff_t", "isize"),
+];
+
+/// Adjusts the names and derives bindgen generates
+#[derive(Debug)]
+struct ShmemCallbacks;
+
+impl bindgen::callbacks::ParseCallbacks for ShmemCallbacks {
+    /// strips the leading underscores of implementation internal struct tags like
+    /// `_shmem_team_config`, so the same struct has the same name across implementations
+    ///
+    /// Only lowercase names are touched; the `_SHMEM_*` constants are the deprecated spellings
+    /// of `SHMEM_*` and renaming them would clash.
+    fn item_name(&self, name: &str) -> Option<String> {
+        let stripped = name.trim_start_matches('_');
+        (stripped != name
+            && stripped.starts_with("shmem")
+            && !stripped.chars().any(|c| c.is_ascii_uppercase()))
+        .then(|| stripped.to_owned())
+    }
+
+    /// the aliases from `INTEGER_ALIASES` are blocklisted, tell bindgen that structs containing
+    /// them can still derive everything a primitive integer implements
+    fn blocklisted_type_implements_trait(
+        &self,
+        name: &str,
+        _derive_trait: bindgen::callbacks::DeriveTrait,
+    ) -> Option<bindgen::callbacks::ImplementsTrait> {
+        INTEGER_ALIASES
+            .iter()
+            .any(|(alias, _)| *alias == name)
+            .then_some(bindgen::callbacks::ImplementsTrait::Yes)
+    }
+}
+
+/// a builder for the bindings of `header` with the probed include paths and defines of `oshmem`
+pub fn builder(oshmem: &Library, header: &Path) -> bindgen::Builder {
+    let mut builder = bindgen::Builder::default()
+        .clang_args(
+            oshmem
+                .include_paths
+                .iter()
+                .map(|p| format!("-I{}", p.to_string_lossy())),
+        )
+        .clang_args(oshmem.defines.iter().map(|def| format!("-D{}", def)))
+        .header(header.to_string_lossy())
+        .prepend_enum_name(false)
+        // Constants like `SHMEM_CMP_EQ` are often defined as expressions bindgen can't evaluate on
+        // its own, let clang compute them. They are `int` arguments in the API, so emit `i32`.
+        .clang_macro_fallback()
+        .default_macro_constant_type(bindgen::MacroTypeVariation::Signed)
+        // Sizes and pointer differences come out as `usize`/`isize`, the other integer aliases
+        // are replaced below.
+        .size_t_is_usize(true)
+        .parse_callbacks(Box::new(ShmemCallbacks))
+        // bindgen only derives these where every field supports them (e.g. no `Hash` for structs
+        // containing floats), so types that can't have them just don't get the impl.
+        .derive_default(true)
+        .derive_partialeq(true)
+        .derive_hash(true)
+        // Only emit the OpenSHMEM API, not everything the headers pull in.
+        .allowlist_function("shmem_.*")
+        .allowlist_type("shmem.*")
+        .allowlist_var("SHMEM.*|_SHMEM.*")
+        // The va_list representation differs between targets (and isn't `Copy` on some), so keep
+        // it and everything taking one out of the bindings.
+        .blocklist_type("va_list|__va_list_tag|__builtin_va_list|__gnuc_va_list")
+        .blocklist_function(".*printf.*");
+    for (alias, primitive) in INTEGER_ALIASES {
+        builder = builder
+            .blocklist_type(alias)
+            .raw_line(format!("pub type {} = {};", alias, primitive));
+    }
+    builder
+}
+
+/// replaces the build directories in `bindings` by `$OUT_DIR` and `$CARGO_MANIFEST_DIR`, for the
+/// `reproducible` feature
+///
+/// bindgen names the headers it read in comments, and the wrapper header sits in the source
+/// tree. Stripped, the bindings for the same headers are identical in any build directory.
+pub fn strip_bindings_paths(bindings: &Path, out_path: &Path, manifest_dir: &Path) {
+    let code = std::fs::read_to_string(bindings).expect("Couldn't read bindings!");
+    let code = strip_build_paths(
+        &code,
+        &[
+            (out_path, "$OUT_DIR"),
+            (manifest_dir, "$CARGO_MANIFEST_DIR"),
+        ],
+    );
+    std::fs::write(bindings, code).expect("Couldn't write bindings!");
+}
@@ -0,0 +1,17 @@
+//! Initializes OpenSHMEM through the raw bindings and prints which PE this is.
+//!
+//! Build it with `cargo build --example hello_pe` and launch it with the runtime's launcher, e.g.
+//! `oshrun -n 4 target/debug/examples/hello_pe`. Building the examples also checks that the
+//! bindings link against the installed library.
+
+use openshmem_sys::{shmem_finalize, shmem_init, shmem_my_pe, shmem_n_pes};
+
+fn main() {
+    // The bindings are plain `extern "C"` declarations, every call into them is unsafe. The
+    // queries are only valid between `shmem_init` and `shmem_finalize`.
+    unsafe {
+        shmem_init();
+        println!("hello from PE {} of {}", shmem_my_pe(), shmem_n_pes());
+        shmem_finalize();
+    }
+}
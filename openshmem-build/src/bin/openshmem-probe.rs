@@ -0,0 +1,55 @@
+//! Runs the probe `openshmem-sys` uses and prints what it found.
+//!
+//! `cargo run -p openshmem-build --bin openshmem-probe` checks the environment without a full
+//! build; the binary belongs to `openshmem-build`, so `-p` is needed from the workspace root. It
+//! honors the same variables as the build script (`OPENSHMEM_DIR`, `OSHMEM_CC`,
+//! `OPENSHMEM_SYS_IMPL`, ...). The `cargo:` lines a build script would print are left out, the
+//! probe's warnings go to stderr.
+
+use std::path::PathBuf;
+use std::process;
+
+use openshmem_build::Probe;
+
+fn print_list(name: &str, items: &[String]) {
+    if items.is_empty() {
+        println!("{:<17}(none)", format!("{}:", name));
+    } else {
+        println!("{:<17}{}", format!("{}:", name), items.join(" "));
+    }
+}
+
+fn print_paths(name: &str, paths: &[PathBuf]) {
+    let items: Vec<_> = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    print_list(name, &items);
+}
+
+fn main() {
+    let lib = Probe::new()
+        .cargo_metadata(false)
+        .probe()
+        .unwrap_or_else(|err| {
+            eprintln!("failed to locate OpenSHMEM: {}", err);
+            process::exit(1);
+        });
+
+    println!();
+    println!(
+        "{:<17}{}",
+        "wrapper:",
+        lib.oshcc.as_deref().unwrap_or("(none)")
+    );
+    println!("{:<17}{}", "implementation:", lib.implementation.name());
+    println!("{:<17}{}", "version:", lib.version);
+    print_list("libs", &lib.libs);
+    print_list("static libs", &lib.static_libs);
+    print_paths("lib paths", &lib.lib_paths);
+    print_paths("include paths", &lib.include_paths);
+    print_list("defines", &lib.defines);
+    print_list("link args", &lib.link_args);
+    print_paths("framework paths", &lib.framework_paths);
+    print_list("frameworks", &lib.frameworks);
+}
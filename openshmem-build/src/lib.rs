@@ -0,0 +1,1429 @@
+//! Locates an OpenSHMEM installation from a build script.
+//!
+//! This is the probe `openshmem-sys` runs, for crates that need the same installation in their
+//! own build scripts (e.g. to compile C code against it). [`probe`] looks the library up the
+//! same way `openshmem-sys` does, [`Probe`] allows changing the wrapper, the package name and the
+//! environment variable prefix. [`strip_build_paths`] helps build scripts generating code keep
+//! it reproducible.
+//!
+//! The `openshmem-probe` binary runs the default probe and prints the result, to check an
+//! environment before building: `cargo run -p openshmem-build --bin openshmem-probe`.
+
+use std::env;
+use std::error::Error;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Output};
+
+/// collects the values of all arguments that start with `prefix`
+///
+/// The values are unquoted, see `unquote_value`.
+fn collect_args_with_prefix(args: &[String], prefix: &str) -> Result<Vec<String>, ProbeError> {
+    args.iter()
+        .filter_map(|arg| Some(unquote_value(arg, arg.strip_prefix(prefix)?)))
+        .collect()
+}
+
+/// removes quotes that survived shell splitting around the `value` of an argument, like in
+/// `-L'"/opt/my libs"'`
+///
+/// Unbalanced quotes are an error naming `arg`; silently dropping e.g. an include path would only
+/// resurface as a confusing missing header later.
+fn unquote_value(arg: &str, value: &str) -> Result<String, ProbeError> {
+    unquote(value).map_err(|err| ProbeError::Unquote {
+        arg: arg.to_owned(),
+        err,
+    })
+}
+
+/// extracts the directories passed as `-rpath <dir>`/`-rpath=<dir>` from `-Wl,` arguments
+fn rpath_dirs(linker_args: &[String]) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    // Flatten across arguments so `-Wl,-rpath -Wl,<dir>` is handled like `-Wl,-rpath,<dir>`.
+    let mut parts = linker_args
+        .iter()
+        .flat_map(|arg| arg.split(','))
+        .filter(|part| *part != "-Wl");
+    while let Some(part) = parts.next() {
+        let part = part.trim_start_matches('-');
+        if let Some(dir) = part.strip_prefix("rpath=") {
+            dirs.push(PathBuf::from(dir));
+        } else if part == "rpath"
+            && let Some(dir) = parts.next()
+        {
+            dirs.push(PathBuf::from(dir));
+        }
+    }
+    dirs
+}
+
+/// A library that the wrapper passed by its full path instead of as `-l<name>`
+#[derive(Debug, PartialEq)]
+enum LibraryFile {
+    /// `<dir>/lib<name>.a`
+    Static { dir: PathBuf, name: String },
+    /// `<dir>/lib<name>.so` or `<dir>/lib<name>.dylib`, possibly with a version suffix
+    Dynamic { dir: PathBuf, name: String },
+    /// an archive or shared object not following the `lib<name>` convention
+    Verbatim(PathBuf),
+}
+
+/// recognizes an argument naming an existing `.a`, `.so` or `.dylib` file
+fn library_file(arg: &str) -> Option<LibraryFile> {
+    if arg.starts_with('-') {
+        return None;
+    }
+    let path = PathBuf::from(arg);
+    if !path.is_file() {
+        return None;
+    }
+
+    let file_name = path.file_name()?.to_str()?.to_owned();
+    let (stem, is_static) = if let Some(stem) = file_name.strip_suffix(".a") {
+        (stem, true)
+    } else if let Some(stem) = file_name.strip_suffix(".dylib") {
+        // macOS puts the version before the extension, `libfoo.40.dylib`.
+        let stem = stem.split_once('.').map_or(stem, |(stem, _)| stem);
+        (stem, false)
+    } else if let Some((stem, version)) = file_name.split_once(".so") {
+        // Accept `libfoo.so` as well as `libfoo.so.40.1`.
+        if !version.is_empty() && !version.starts_with('.') {
+            return None;
+        }
+        (stem, false)
+    } else {
+        return None;
+    };
+
+    let (Some(name), Some(dir)) = (stem.strip_prefix("lib"), path.parent()) else {
+        return Some(LibraryFile::Verbatim(path.clone()));
+    };
+    let dir = dir.to_path_buf();
+    let name = name.to_owned();
+    Some(if is_static {
+        LibraryFile::Static { dir, name }
+    } else {
+        LibraryFile::Dynamic { dir, name }
+    })
+}
+
+/// The link related parts of a compiler command line, in the order they appeared
+#[derive(Debug, Default)]
+struct LinkLine {
+    libs: Vec<String>,
+    static_libs: Vec<String>,
+    lib_paths: Vec<PathBuf>,
+    link_args: Vec<String>,
+    framework_paths: Vec<PathBuf>,
+    frameworks: Vec<String>,
+    /// flags that are neither compile-only nor understood as link flags
+    unknown_flags: Vec<String>,
+}
+
+/// Prefixes of flags that only matter when compiling, so the link line can ignore them
+const COMPILE_FLAG_PREFIXES: &[&str] = &["-I", "-D", "-U", "-O", "-g", "-f", "-m", "-std=", "-W"];
+
+/// splits a command line printed by the wrapper into arguments, like a shell would
+fn split_command_line(line: &str) -> Result<Vec<String>, ProbeError> {
+    shell_words::split(line).map_err(|err| ProbeError::SplitFailed {
+        line: line.trim().to_owned(),
+        err,
+    })
+}
+
+/// walks the command line left to right so that the relative order of the libraries survives,
+/// which matters when linking archives
+fn parse_link_line(args: &[String]) -> Result<LinkLine, ProbeError> {
+    let mut line = LinkLine::default();
+    let mut args = args.iter().cloned();
+    while let Some(arg) = args.next() {
+        if let Some(lib) = arg.strip_prefix("-l") {
+            line.libs.push(unquote_value(&arg, lib)?);
+        } else if let Some(dir) = arg.strip_prefix("-L") {
+            line.lib_paths
+                .push(PathBuf::from(unquote_value(&arg, dir)?));
+        } else if let Some(dir) = arg.strip_prefix("-F") {
+            line.framework_paths
+                .push(PathBuf::from(unquote_value(&arg, dir)?));
+        } else if arg == "-framework" {
+            // macOS frameworks come as a `-framework <name>` pair.
+            if let Some(name) = args.next() {
+                line.frameworks.push(name);
+            }
+        } else if arg.starts_with("-Wl,") {
+            line.link_args.push(arg);
+        } else if arg == "-pthread" {
+            // Also links libpthread, missing it shows up as undefined `pthread_*` symbols.
+            if !line.link_args.contains(&arg) {
+                line.link_args.push(arg);
+            }
+        } else if let Some(file) = library_file(&arg) {
+            match file {
+                LibraryFile::Static { dir, name } => {
+                    line.lib_paths.push(dir);
+                    line.static_libs.push(name.clone());
+                    line.libs.push(name);
+                }
+                LibraryFile::Dynamic { dir, name } => {
+                    line.lib_paths.push(dir);
+                    line.libs.push(name);
+                }
+                LibraryFile::Verbatim(path) => {
+                    line.link_args.push(path.to_string_lossy().into_owned())
+                }
+            }
+        } else if arg.starts_with('-')
+            && !COMPILE_FLAG_PREFIXES
+                .iter()
+                .any(|prefix| arg.starts_with(prefix))
+        {
+            line.unknown_flags.push(arg);
+        }
+    }
+
+    // The runtime search path is usually where the libraries live, so search there too.
+    for dir in rpath_dirs(&line.link_args) {
+        if !line.lib_paths.contains(&dir) {
+            line.lib_paths.push(dir);
+        }
+    }
+    Ok(line)
+}
+
+/// An argument printed by the wrapper opens a quote it doesn't close
+#[derive(Debug, PartialEq)]
+pub struct UnquoteError {
+    quote: char,
+}
+
+impl UnquoteError {
+    fn new(quote: char) -> UnquoteError {
+        UnquoteError { quote }
+    }
+}
+
+impl fmt::Display for UnquoteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Quotes '{}' not closed.", self.quote)
+    }
+}
+
+impl Error for UnquoteError {}
+
+fn unquote(s: &str) -> Result<String, UnquoteError> {
+    let Some(quote) = s.chars().next().filter(|c| matches!(c, '"' | '\'' | '`')) else {
+        return Ok(String::from(s));
+    };
+    // A lone quote character is taken literally.
+    if s.len() < 2 {
+        return Ok(String::from(s));
+    }
+
+    match s[1..].strip_suffix(quote) {
+        Some(inner) => Ok(String::from(inner)),
+        None => Err(UnquoteError::new(quote)),
+    }
+}
+
+/// Reasons why probing did not produce a usable `Library`
+#[derive(Debug)]
+pub enum ProbeError {
+    /// the wrapper could not be found on `PATH`
+    WrapperNotFound,
+    /// the wrapper could not be executed for some other reason
+    Io(std::io::Error),
+    /// the wrapper ran but exited unsuccessfully
+    WrapperFailed { status: ExitStatus, stderr: String },
+    /// the wrapper printed something that is not valid UTF-8
+    NonUtf8Output,
+    /// an argument printed by the wrapper has unbalanced quotes
+    Unquote { arg: String, err: UnquoteError },
+    /// a command line printed by the wrapper can't be split into arguments, e.g. because a
+    /// quote is never closed
+    SplitFailed {
+        line: String,
+        err: shell_words::ParseError,
+    },
+    /// the wrapper was not found and pkg-config doesn't know `package` either
+    NotFound {
+        wrapper: String,
+        package: String,
+        err: Box<pkg_config::Error>,
+    },
+    /// the `<prefix>_IMPL` variable names no known implementation
+    UnknownImplementation(String),
+    /// Cray was requested but `CRAY_OPENSHMEMX_DIR` is not set
+    CrayDirNotSet,
+    /// sourcing the `<prefix>_ENV_SCRIPT` before running the wrapper failed
+    EnvScriptFailed {
+        script: PathBuf,
+        status: ExitStatus,
+        stderr: String,
+    },
+    /// line `line` of the site configuration file `path` could not be parsed
+    SiteConfig {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProbeError::WrapperNotFound => write!(f, "compiler wrapper not found"),
+            ProbeError::Io(err) => write!(f, "{}", err),
+            ProbeError::WrapperFailed { status, stderr } => {
+                write!(f, "compiler wrapper exited with {}", status)?;
+                if !stderr.trim().is_empty() {
+                    write!(f, ":\n{}", stderr.trim_end())?;
+                }
+                Ok(())
+            }
+            ProbeError::NonUtf8Output => write!(f, "compiler wrapper output is not valid UTF-8"),
+            ProbeError::Unquote { arg, err } => {
+                write!(f, "can't parse argument `{}`: {}", arg, err)
+            }
+            ProbeError::SplitFailed { line, err } => {
+                write!(f, "can't split `{}` into arguments: {}", line, err)
+            }
+            ProbeError::NotFound {
+                wrapper,
+                package,
+                err,
+            } => write!(
+                f,
+                "`{}` was not found and pkg-config could not locate `{}`: {}",
+                wrapper, package, err
+            ),
+            ProbeError::UnknownImplementation(name) => write!(
+                f,
+                "unknown implementation `{}`, expected one of openmpi, sandia, osss, cray",
+                name
+            ),
+            ProbeError::CrayDirNotSet => write!(
+                f,
+                "Cray OpenSHMEMX needs CRAY_OPENSHMEMX_DIR (`module load cray-openshmemx`)"
+            ),
+            ProbeError::EnvScriptFailed {
+                script,
+                status,
+                stderr,
+            } => {
+                write!(f, "sourcing `{}` exited with {}", script.display(), status)?;
+                if !stderr.trim().is_empty() {
+                    write!(f, ":\n{}", stderr.trim_end())?;
+                }
+                Ok(())
+            }
+            ProbeError::SiteConfig {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{}: {}", path.display(), line, message),
+        }
+    }
+}
+
+impl Error for ProbeError {}
+
+impl From<std::io::Error> for ProbeError {
+    fn from(err: std::io::Error) -> ProbeError {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            ProbeError::WrapperNotFound
+        } else {
+            ProbeError::Io(err)
+        }
+    }
+}
+
+/// The OpenSHMEM implementation behind a `Library`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Implementation {
+    /// OSHMEM, shipped with Open MPI
+    OpenMpi,
+    /// Sandia OpenSHMEM (SOS)
+    Sandia,
+    /// OSSS OpenSHMEM over UCX
+    Osss,
+    /// Cray/HPE OpenSHMEMX
+    Cray,
+    /// an implementation none of the above heuristics recognized
+    Unknown,
+}
+
+impl Implementation {
+    /// the name exported as `DEP_OPENSHMEM_IMPLEMENTATION`
+    pub fn name(self) -> &'static str {
+        match self {
+            Implementation::OpenMpi => "openmpi",
+            Implementation::Sandia => "sandia",
+            Implementation::Osss => "osss",
+            Implementation::Cray => "cray",
+            Implementation::Unknown => "unknown",
+        }
+    }
+
+    /// parses the names accepted by `OPENSHMEM_SYS_IMPL`
+    pub fn from_name(name: &str) -> Option<Implementation> {
+        match name.to_lowercase().as_str() {
+            "openmpi" | "oshmem" => Some(Implementation::OpenMpi),
+            "sandia" | "sos" => Some(Implementation::Sandia),
+            "osss" => Some(Implementation::Osss),
+            "cray" => Some(Implementation::Cray),
+            _ => None,
+        }
+    }
+
+    /// the pkg-config package the implementation installs
+    pub fn pkg_config_name(self) -> &'static str {
+        match self {
+            Implementation::Sandia => "sandia-openshmem",
+            Implementation::Osss => "osss-ucx",
+            _ => "oshmem",
+        }
+    }
+}
+
+/// guesses the implementation from the wrapper output (or any other text naming the install)
+/// and the libraries it links
+fn detect_implementation(text: &str, libs: &[String]) -> Implementation {
+    let text = text.to_lowercase();
+    let links = |name: &str| libs.iter().any(|lib| lib == name);
+
+    if text.contains("cray") {
+        Implementation::Cray
+    } else if text.contains("open mpi") || text.contains("openmpi") || links("oshmem") {
+        Implementation::OpenMpi
+    } else if text.contains("sandia") || links("sma") {
+        Implementation::Sandia
+    } else if text.contains("osss") || links("shmem") {
+        Implementation::Osss
+    } else {
+        Implementation::Unknown
+    }
+}
+
+/// prints the build script instruction `cargo:<key>=<value>` if `cargo_metadata` is set
+///
+/// Otherwise, as when not running in a build script, warnings go to stderr as plain messages
+/// and everything else is dropped.
+fn emit(cargo_metadata: bool, key: &str, value: impl fmt::Display) {
+    if cargo_metadata {
+        println!("cargo:{}={}", key, value);
+    } else if key == "warning" {
+        eprintln!("warning: {}", value);
+    }
+}
+
+/// what the shell running an environment script prints to stderr once the script was sourced
+const ENV_SCRIPT_SOURCED: &str = "openshmem-build: environment script sourced";
+
+/// runs `cmd`, inside a shell that sources `env_script` first if given
+///
+/// Anything that needs the environment the wrapper runs in should go through this, like the
+/// wrapper itself, `oshinfo` or programs built with the wrapper. The environment variables set
+/// on `cmd` are set for the script as well. Errors from sourcing the script are reported as
+/// [`ProbeError::EnvScriptFailed`], told apart from the program's own by a marker the shell
+/// prints in between. A program the script's `PATH` doesn't have is
+/// [`ProbeError::WrapperNotFound`].
+pub fn wrapper_output(cmd: &mut Command, env_script: Option<&Path>) -> Result<Output, ProbeError> {
+    let Some(script) = env_script else {
+        return Ok(cmd.output()?);
+    };
+    // `.` searches `PATH` for a name without a slash, so pass the script by its full path.
+    let sourced = script
+        .canonicalize()
+        .unwrap_or_else(|_| script.to_path_buf());
+    // The script and the command line are passed as arguments so none of them need quoting.
+    let mut shell = Command::new("sh");
+    shell
+        .arg("-c")
+        .arg(format!(
+            ". \"$1\" || exit; echo '{}' >&2; shift; exec \"$@\"",
+            ENV_SCRIPT_SOURCED
+        ))
+        .arg("sh")
+        .arg(&sourced)
+        .arg(cmd.get_program())
+        .args(cmd.get_args());
+    for (name, value) in cmd.get_envs() {
+        match value {
+            Some(value) => shell.env(name, value),
+            None => shell.env_remove(name),
+        };
+    }
+    if let Some(dir) = cmd.get_current_dir() {
+        shell.current_dir(dir);
+    }
+    let mut cmd = shell.output()?;
+
+    let stderr = String::from_utf8_lossy(&cmd.stderr).into_owned();
+    let Some((script_stderr, wrapper_stderr)) = stderr.split_once(ENV_SCRIPT_SOURCED) else {
+        return Err(ProbeError::EnvScriptFailed {
+            script: script.to_path_buf(),
+            status: cmd.status,
+            stderr,
+        });
+    };
+    // `exec` failing means the wrapper isn't on the `PATH` the script set up.
+    if cmd.status.code() == Some(127) {
+        return Err(ProbeError::WrapperNotFound);
+    }
+    cmd.stderr = format!(
+        "{}{}",
+        script_stderr,
+        wrapper_stderr.trim_start_matches('\n')
+    )
+    .into_bytes();
+    Ok(cmd)
+}
+
+/// every file named `program` in the directories of `path`, in search order
+///
+/// The same wrapper is often reachable through several entries, e.g. a symlinked prefix, so
+/// entries resolving to the same file are only listed once.
+fn find_all_in_path(program: &str, path: &OsStr) -> Vec<PathBuf> {
+    let mut found: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for dir in env::split_paths(path) {
+        let candidate = dir.join(program);
+        if !candidate.is_file() {
+            continue;
+        }
+        let resolved = candidate
+            .canonicalize()
+            .unwrap_or_else(|_| candidate.clone());
+        if !found.iter().any(|(_, seen)| *seen == resolved) {
+            found.push((candidate, resolved));
+        }
+    }
+    found.into_iter().map(|(candidate, _)| candidate).collect()
+}
+
+/// warns which wrapper the probe ran and which version it reported, and about other wrappers of
+/// the same name on `PATH`
+///
+/// With several OpenSHMEM modules loaded the wrappers shadow each other, and the wrong one only
+/// shows up as a confusing link or runtime error. The `PATH` is the one the wrapper ran with,
+/// after sourcing `env_script` if there is one.
+fn report_wrapper(oshcc: &str, version: &str, env_script: Option<&Path>, cargo_metadata: bool) {
+    let path = match env_script {
+        Some(_) => wrapper_output(
+            Command::new("sh").args(["-c", "printf '%s' \"$PATH\""]),
+            env_script,
+        )
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| OsString::from(String::from_utf8_lossy(&out.stdout).into_owned())),
+        None => env::var_os("PATH"),
+    }
+    .unwrap_or_default();
+
+    let name = Path::new(oshcc)
+        .file_name()
+        .map_or_else(|| oshcc.into(), |name| name.to_string_lossy());
+    let on_path = find_all_in_path(&name, &path);
+    let used = if oshcc.contains(std::path::is_separator) {
+        Some(PathBuf::from(oshcc))
+    } else {
+        on_path.first().cloned()
+    };
+    let Some(used) = used else {
+        return;
+    };
+    emit(
+        cargo_metadata,
+        "warning",
+        format_args!(
+            "using the OpenSHMEM wrapper `{}` (spec version {})",
+            used.display(),
+            version
+        ),
+    );
+
+    let same_file = |a: &Path, b: &Path| match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    };
+    let others: Vec<_> = on_path
+        .iter()
+        .filter(|other| !same_file(other, &used))
+        .map(|other| other.display().to_string())
+        .collect();
+    if !others.is_empty() {
+        emit(
+            cargo_metadata,
+            "warning",
+            format_args!(
+                "other `{}` wrappers on PATH: {}; if the wrong OpenSHMEM is linked, check which modules are loaded or set OSHMEM_CC",
+                name,
+                others.join(", ")
+            ),
+        );
+    }
+}
+
+/// runs the wrapper with a single `arg`, returning the output if it succeeded
+fn run_wrapper(oshcc: &str, arg: &str, env_script: Option<&Path>) -> Result<Output, ProbeError> {
+    let cmd = wrapper_output(Command::new(oshcc).arg(arg), env_script)?;
+    if !cmd.status.success() {
+        return Err(ProbeError::WrapperFailed {
+            status: cmd.status,
+            stderr: String::from_utf8_lossy(&cmd.stderr).into_owned(),
+        });
+    }
+    Ok(cmd)
+}
+
+/// runs the wrapper with a single `arg` and returns what it printed to stdout
+fn wrapper_text(oshcc: &str, arg: &str, env_script: Option<&Path>) -> Result<String, ProbeError> {
+    let cmd = run_wrapper(oshcc, arg, env_script)?;
+    String::from_utf8(cmd.stdout).map_err(|_| ProbeError::NonUtf8Output)
+}
+
+/// runs the wrapper with a single `arg` and splits the command line it printed into arguments
+fn wrapper_args(
+    oshcc: &str,
+    arg: &str,
+    env_script: Option<&Path>,
+) -> Result<Vec<String>, ProbeError> {
+    let cmd = run_wrapper(oshcc, arg, env_script)?;
+    let stdout = String::from_utf8(cmd.stdout).map_err(|_| ProbeError::NonUtf8Output)?;
+    let mut args = split_command_line(&stdout)?;
+    // Some wrappers print the command line to stderr instead. Diagnostics there needn't split
+    // like a command line, only a stderr that does is taken as one.
+    if !has_build_flags(&args)
+        && let Ok(stderr) = String::from_utf8(cmd.stderr)
+        && let Ok(stderr_args) = split_command_line(&stderr)
+        && has_build_flags(&stderr_args)
+    {
+        args.extend(stderr_args);
+    }
+    Ok(args)
+}
+
+/// whether a command line contains any of the flags the probe is interested in
+fn has_build_flags(args: &[String]) -> bool {
+    args.iter()
+        .any(|arg| arg.starts_with("-l") || arg.starts_with("-L") || arg.starts_with("-I"))
+}
+
+fn probe_via_oshcc(
+    oshcc: &str,
+    env_script: Option<&Path>,
+    cargo_metadata: bool,
+) -> Result<Library, ProbeError> {
+    // Capture the output of `oshcc -show`. This usually gives the actual compiler command line
+    // invoked by the `oshcc` compiler wrapper.
+    let mut args = wrapper_args(oshcc, "-show", env_script)?;
+    if !has_build_flags(&args) {
+        // Some Open MPI versions only answer the split `--showme:*` queries usefully. Each output
+        // is split on its own, so a quote left open in one can't swallow the other. Wrappers
+        // that don't know the queries keep the `-show` line.
+        match (
+            wrapper_args(oshcc, "--showme:compile", env_script),
+            wrapper_args(oshcc, "--showme:link", env_script),
+        ) {
+            (Ok(compile), Ok(link)) => args = [compile, link].concat(),
+            (Err(err @ ProbeError::SplitFailed { .. }), _)
+            | (_, Err(err @ ProbeError::SplitFailed { .. })) => return Err(err),
+            _ => {}
+        }
+    }
+    // Collect the libraries that an OpenSHMEM C program should be linked to, with their search
+    // directories and any arguments meant for the linker itself...
+    let link = parse_link_line(&args)?;
+    for flag in &link.unknown_flags {
+        emit(
+            cargo_metadata,
+            "warning",
+            format_args!(
+                "ignoring `{}` from `{}`, pass it with RUSTFLAGS if the link needs it",
+                flag, oshcc
+            ),
+        );
+    }
+    // ... and the preprocessor definitions, both `NAME` and `NAME=value`...
+    let defines = collect_args_with_prefix(&args, "-D")?;
+    // ... and the header search directories.
+    let headerdirs = collect_args_with_prefix(&args, "-I")?
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+    // The `-show` line rarely names the implementation, but the version banner usually does.
+    let banner = wrapper_text(oshcc, "--showme:version", env_script).unwrap_or_default();
+    let implementation =
+        detect_implementation(&format!("{}\n{}", args.join(" "), banner), &link.libs);
+
+    Ok(Library {
+        oshcc: Some(oshcc.to_string()),
+        implementation,
+        libs: link.libs,
+        static_libs: link.static_libs,
+        link_args: link.link_args,
+        framework_paths: link.framework_paths,
+        frameworks: link.frameworks,
+        lib_paths: link.lib_paths,
+        include_paths: headerdirs,
+        defines,
+        version: String::from("unknown"),
+        env_script: None,
+        _priv: (),
+    })
+}
+
+fn probe_via_pkg_config(name: &str, cargo_metadata: bool) -> Result<Library, pkg_config::Error> {
+    // Don't let pkg-config print its own link lines, `main` emits them from the `Library`. Its
+    // `rerun-if-env-changed` lines are only wanted in a build script.
+    let lib = pkg_config::Config::new()
+        .cargo_metadata(false)
+        .env_metadata(cargo_metadata)
+        .probe(name)?;
+
+    Ok(Library {
+        oshcc: None,
+        implementation: detect_implementation(name, &lib.libs),
+        libs: lib.libs,
+        static_libs: Vec::new(),
+        link_args: Vec::new(),
+        framework_paths: lib.framework_paths,
+        frameworks: lib.frameworks,
+        lib_paths: lib.link_paths,
+        include_paths: lib.include_paths,
+        defines: lib
+            .defines
+            .into_iter()
+            .map(|(name, value)| match value {
+                Some(value) => format!("{}={}", name, value),
+                None => name,
+            })
+            .collect(),
+        // This is the package version, e.g. Open MPI's 4.1.5, not the spec version.
+        version: String::from("unknown"),
+        env_script: None,
+        _priv: (),
+    })
+}
+
+/// assumes the usual `include`/`lib` layout below an install prefix
+fn probe_via_prefix(prefix: &Path, implementation: Implementation) -> Library {
+    let libs = match implementation {
+        // Both Sandia OpenSHMEM and Cray name their library `libsma`.
+        Implementation::Sandia | Implementation::Cray => vec![String::from("sma")],
+        Implementation::Osss => vec![String::from("shmem")],
+        Implementation::OpenMpi | Implementation::Unknown => {
+            vec![String::from("oshmem"), String::from("mpi")]
+        }
+    };
+
+    Library {
+        oshcc: None,
+        implementation,
+        libs,
+        static_libs: Vec::new(),
+        link_args: Vec::new(),
+        framework_paths: Vec::new(),
+        frameworks: Vec::new(),
+        lib_paths: vec![prefix.join("lib")],
+        include_paths: vec![prefix.join("include")],
+        defines: Vec::new(),
+        version: String::from("unknown"),
+        env_script: None,
+        _priv: (),
+    }
+}
+
+/// adds the libraries and search paths of `extra` that `lib` is missing, keeping the order and
+/// everything else of `lib`
+///
+/// Nothing is merged unless the two share a library or header directory (or `lib` has none),
+/// a pkg-config file for some other installation must not leak into the link line.
+fn merge_library(lib: &mut Library, extra: Library) {
+    let shares = |ours: &[PathBuf], theirs: &[PathBuf]| theirs.iter().any(|dir| ours.contains(dir));
+    let same_install = (lib.lib_paths.is_empty() && lib.include_paths.is_empty())
+        || shares(&lib.lib_paths, &extra.lib_paths)
+        || shares(&lib.include_paths, &extra.include_paths);
+    if !same_install {
+        return;
+    }
+
+    fn union<T: PartialEq>(ours: &mut Vec<T>, theirs: Vec<T>) {
+        for item in theirs {
+            if !ours.contains(&item) {
+                ours.push(item);
+            }
+        }
+    }
+    union(&mut lib.libs, extra.libs);
+    union(&mut lib.lib_paths, extra.lib_paths);
+    union(&mut lib.include_paths, extra.include_paths);
+}
+
+/// whether one of `dirs` has a shared or static library called `name`
+fn library_exists(dirs: &[PathBuf], name: &str) -> bool {
+    dirs.iter().any(|dir| {
+        ["so", "dylib", "a"]
+            .iter()
+            .any(|ext| dir.join(format!("lib{}.{}", name, ext)).is_file())
+    })
+}
+
+/// adds whichever of `oshmem` and `mpi` the link line of an Open MPI install is missing, if the
+/// library is in the search paths
+///
+/// OSHMEM is built on top of libmpi and needs both, but some wrappers only report one of them.
+fn add_open_mpi_libs(lib: &mut Library) {
+    if !lib.libs.iter().any(|l| l == "oshmem") && library_exists(&lib.lib_paths, "oshmem") {
+        // libmpi comes last, liboshmem depends on it.
+        let at = lib
+            .libs
+            .iter()
+            .position(|l| l == "mpi")
+            .unwrap_or(lib.libs.len());
+        lib.libs.insert(at, String::from("oshmem"));
+    }
+    if !lib.libs.iter().any(|l| l == "mpi") && library_exists(&lib.lib_paths, "mpi") {
+        lib.libs.push(String::from("mpi"));
+    }
+}
+
+/// subdirectories of an install prefix that may hold `shmem.h`
+const PREFIX_INCLUDE_DIRS: &[&str] = &["include", "include/shmem"];
+
+/// the install prefixes the probe knows about: `SHMEM_HOME`, `extra` and the parents of the
+/// `lib*` search paths
+fn install_prefixes(lib: &Library, extra: Option<&Path>, cargo_metadata: bool) -> Vec<PathBuf> {
+    emit(cargo_metadata, "rerun-if-env-changed", "SHMEM_HOME");
+
+    let mut prefixes: Vec<PathBuf> = env::var_os("SHMEM_HOME")
+        .map(PathBuf::from)
+        .into_iter()
+        .collect();
+    prefixes.extend(extra.map(Path::to_path_buf));
+    prefixes.extend(lib.lib_paths.iter().filter_map(|dir| {
+        let name = dir.file_name()?.to_str()?;
+        if !name.starts_with("lib") {
+            return None;
+        }
+        dir.parent().map(Path::to_path_buf)
+    }));
+    prefixes
+}
+
+/// appends the [`PREFIX_INCLUDE_DIRS`] of `prefixes` that contain `shmem.h` to the include
+/// paths, for installs whose wrapper doesn't name all of them
+fn add_prefix_include_dirs(lib: &mut Library, prefixes: &[PathBuf]) {
+    for prefix in prefixes {
+        for subdir in PREFIX_INCLUDE_DIRS {
+            let dir = prefix.join(subdir);
+            if dir.join("shmem.h").is_file() && !lib.include_paths.contains(&dir) {
+                lib.include_paths.push(dir);
+            }
+        }
+    }
+}
+
+/// finds the first `<major>.<minor>` number in a line that talks about the spec/API level
+fn parse_spec_version(text: &str) -> Option<String> {
+    text.lines()
+        .filter(|line| {
+            let line = line.to_lowercase();
+            line.contains("spec") || line.contains("api")
+        })
+        .flat_map(|line| line.split(|c: char| !c.is_ascii_digit() && c != '.'))
+        .find_map(|word| {
+            let mut parts = word.split('.');
+            let major = parts.next().filter(|p| !p.is_empty())?;
+            let minor = parts.next().filter(|p| !p.is_empty())?;
+            Some(format!("{}.{}", major, minor))
+        })
+}
+
+/// splits a `<major>.<minor>` version as produced by `parse_spec_version`
+fn version_pair(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// runs `program` with `args` after sourcing `env_script`, returning its stdout if it ran
+/// successfully
+fn run_for_output(program: &str, args: &[&str], env_script: Option<&Path>) -> Option<String> {
+    let cmd = wrapper_output(Command::new(program).args(args), env_script).ok()?;
+    if !cmd.status.success() {
+        return None;
+    }
+    String::from_utf8(cmd.stdout).ok()
+}
+
+/// reads `SHMEM_MAJOR_VERSION`/`SHMEM_MINOR_VERSION` out of the first `shmem.h` found
+fn version_from_header(include_paths: &[PathBuf]) -> Option<String> {
+    let header = include_paths
+        .iter()
+        .flat_map(|dir| [dir.join("shmem.h"), dir.join("mpp").join("shmem.h")])
+        .find(|path| path.is_file())?;
+    let contents = std::fs::read_to_string(header).ok()?;
+
+    let define = |name: &str| {
+        contents.lines().find_map(|line| {
+            let mut words = line.split_whitespace();
+            if words.next()? != "#define" || words.next()? != name {
+                return None;
+            }
+            words.next()?.parse::<u32>().ok()
+        })
+    };
+
+    Some(format!(
+        "{}.{}",
+        define("SHMEM_MAJOR_VERSION")?,
+        define("SHMEM_MINOR_VERSION")?
+    ))
+}
+
+/// A value in the site configuration file
+enum ConfigValue {
+    String(String),
+    Array(Vec<String>),
+}
+
+/// parses a basic TOML string, `"..."` with `\"` and `\\` escapes, returning it and the rest of
+/// `text`
+fn parse_config_string(text: &str) -> Result<(String, &str), String> {
+    let Some(text) = text.strip_prefix('"') else {
+        return Err(String::from("expected a string in double quotes"));
+    };
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &text[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, c @ ('"' | '\\'))) => value.push(c),
+                _ => return Err(String::from("only \\\" and \\\\ escapes are supported")),
+            },
+            c => value.push(c),
+        }
+    }
+    Err(String::from("unterminated string"))
+}
+
+/// parses the value of a `key = value` line, a string or a single line array of strings
+fn parse_config_value(text: &str) -> Result<ConfigValue, String> {
+    let (value, rest) = if let Some(mut rest) = text.strip_prefix('[') {
+        let mut items = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(after) = rest.strip_prefix(']') {
+                break (ConfigValue::Array(items), after);
+            }
+            let (item, after) = parse_config_string(rest)?;
+            items.push(item);
+            rest = after.trim_start();
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after;
+            } else if !rest.starts_with(']') {
+                return Err(String::from("expected `,` or `]` after an array item"));
+            }
+        }
+    } else {
+        let (value, rest) = parse_config_string(text)?;
+        (ConfigValue::String(value), rest)
+    };
+    let rest = rest.trim_start();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(format!("unexpected `{}` after the value", rest));
+    }
+    Ok(value)
+}
+
+/// tries to determine the OpenSHMEM spec version implemented by `lib`
+fn detect_version(lib: &Library, env_script: Option<&Path>) -> Option<String> {
+    lib.oshcc
+        .as_deref()
+        .and_then(|oshcc| wrapper_text(oshcc, "--showme:version", env_script).ok())
+        .and_then(|out| parse_spec_version(&out))
+        .or_else(|| {
+            run_for_output("oshinfo", &[], env_script).and_then(|out| parse_spec_version(&out))
+        })
+        .or_else(|| version_from_header(&lib.include_paths))
+}
+
+/// Result of a successfull probe
+#[allow(clippy::manual_non_exhaustive)]
+#[derive(Clone, Debug)]
+pub struct Library {
+    /// Path to compiler capable of building MPI programs
+    pub oshcc: Option<String>,
+    /// Which OpenSHMEM implementation was found
+    pub implementation: Implementation,
+    /// Names of the native MPI libraries that need to be linked
+    pub libs: Vec<String>,
+    /// Names of the entries in `libs` that were given as archive paths and are always linked
+    /// statically
+    pub static_libs: Vec<String>,
+    /// Extra arguments passed to the linker verbatim
+    pub link_args: Vec<String>,
+    /// Search path for macOS frameworks
+    pub framework_paths: Vec<PathBuf>,
+    /// Names of the macOS frameworks that need to be linked
+    pub frameworks: Vec<String>,
+    /// Search path for native MPI libraries
+    pub lib_paths: Vec<PathBuf>,
+    /// Search path for C header files
+    pub include_paths: Vec<PathBuf>,
+    /// Preprocessor definitions as `NAME` or `NAME=value`
+    pub defines: Vec<String>,
+    /// The OpenSHMEM spec version implemented by the library, e.g. `1.5`
+    pub version: String,
+    /// The `<prefix>_ENV_SCRIPT` the wrapper ran under, to run anything else that needs its
+    /// environment with [`wrapper_output`]
+    pub env_script: Option<PathBuf>,
+    _priv: (),
+}
+
+impl Library {
+    /// a library configured by hand instead of probed, linking `libs` found in `lib_paths`
+    pub fn new(libs: Vec<String>, lib_paths: Vec<PathBuf>) -> Library {
+        Library {
+            oshcc: None,
+            implementation: Implementation::Unknown,
+            libs,
+            static_libs: Vec::new(),
+            link_args: Vec::new(),
+            framework_paths: Vec::new(),
+            frameworks: Vec::new(),
+            lib_paths,
+            include_paths: Vec::new(),
+            defines: Vec::new(),
+            version: String::from("unknown"),
+            env_script: None,
+            _priv: (),
+        }
+    }
+
+    /// `version` as `(major, minor)`, if it is known
+    pub fn spec_version(&self) -> Option<(u32, u32)> {
+        version_pair(&self.version)
+    }
+
+    /// reads a library described by a site configuration file instead of probing for it
+    ///
+    /// The file is a small subset of TOML, one `key = value` per line with `#` comments, where
+    /// the values are strings or single line arrays of strings:
+    ///
+    /// ```toml
+    /// libs = ["sma", "pmi_simple"]
+    /// lib_paths = ["/opt/sos/lib"]
+    /// include_paths = ["/opt/sos/include"]
+    /// # optional
+    /// implementation = "sandia"
+    /// version = "1.5"
+    /// ```
+    ///
+    /// `libs` is required. Without `version` it is read from `shmem.h` in `include_paths`.
+    pub fn from_site_config(path: &Path) -> Result<Library, ProbeError> {
+        let text = std::fs::read_to_string(path).map_err(ProbeError::Io)?;
+        let error = |line: usize, message: String| ProbeError::SiteConfig {
+            path: path.to_path_buf(),
+            line,
+            message,
+        };
+
+        let mut oshmem = Library::new(Vec::new(), Vec::new());
+        let mut has_libs = false;
+        for (i, line) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(error(line_no, String::from("expected `key = value`")));
+            };
+            let key = key.trim();
+            let value = parse_config_value(value.trim()).map_err(|msg| error(line_no, msg))?;
+            match (key, value) {
+                ("libs", ConfigValue::Array(libs)) => {
+                    oshmem.libs = libs;
+                    has_libs = true;
+                }
+                ("lib_paths", ConfigValue::Array(dirs)) => {
+                    oshmem.lib_paths = dirs.into_iter().map(PathBuf::from).collect();
+                }
+                ("include_paths", ConfigValue::Array(dirs)) => {
+                    oshmem.include_paths = dirs.into_iter().map(PathBuf::from).collect();
+                }
+                ("implementation", ConfigValue::String(name)) => {
+                    oshmem.implementation = Implementation::from_name(&name).ok_or_else(|| {
+                        error(line_no, ProbeError::UnknownImplementation(name).to_string())
+                    })?;
+                }
+                ("version", ConfigValue::String(version)) => oshmem.version = version,
+                ("libs" | "lib_paths" | "include_paths", _) => {
+                    return Err(error(
+                        line_no,
+                        format!("`{}` must be an array of strings", key),
+                    ));
+                }
+                ("implementation" | "version", _) => {
+                    return Err(error(line_no, format!("`{}` must be a string", key)));
+                }
+                _ => return Err(error(line_no, format!("unknown key `{}`", key))),
+            }
+        }
+        if !has_libs {
+            return Err(error(text.lines().count(), String::from("missing `libs`")));
+        }
+        if oshmem.version == "unknown"
+            && let Some(version) = version_from_header(&oshmem.include_paths)
+        {
+            oshmem.version = version;
+        }
+        Ok(oshmem)
+    }
+}
+
+/// Configures how [`Probe::probe`] looks for the installation
+///
+/// The defaults are what `openshmem-sys` uses itself: `OPENSHMEM_DIR` or `CRAY_OPENSHMEMX_DIR`
+/// if set, otherwise the wrapper from `OSHMEM_CC_<target>`, `OSHMEM_CC`, `OSHCC` or `oshcc`
+/// (or `CC_<target>` if that isn't installed), falling back to the pkg-config package from
+/// `OSHMEM_PKG_CONFIG`, or the one the requested [`Implementation`] installs (`oshmem` if none
+/// is).
+///
+/// If the wrapper works and pkg-config knows the same installation, the libraries and search
+/// paths only pkg-config reports are added to the wrapper's.
+///
+/// If `<prefix>_ENV_SCRIPT` names a shell script, the wrapper runs in `sh` after sourcing it,
+/// for sites where it only works once a module is loaded. So does `oshinfo`, and the script ends
+/// up in [`Library::env_script`] for whatever else the build runs.
+#[derive(Clone, Debug)]
+pub struct Probe {
+    wrapper: Option<String>,
+    env_prefix: String,
+    pkg_config_name: Option<String>,
+    implementation: Option<Implementation>,
+    cargo_metadata: bool,
+}
+
+impl Default for Probe {
+    fn default() -> Probe {
+        Probe::new()
+    }
+}
+
+impl Probe {
+    pub fn new() -> Probe {
+        Probe {
+            wrapper: None,
+            env_prefix: String::from("OPENSHMEM_SYS"),
+            pkg_config_name: None,
+            implementation: None,
+            cargo_metadata: true,
+        }
+    }
+
+    /// runs `wrapper` instead of the one named in the environment
+    pub fn wrapper(&mut self, wrapper: impl Into<String>) -> &mut Probe {
+        self.wrapper = Some(wrapper.into());
+        self
+    }
+
+    /// the prefix of the variables specific to this probe, `<prefix>_IMPL`, `<prefix>_LIBS` and
+    /// `<prefix>_ENV_SCRIPT`; `OPENSHMEM_SYS` by default
+    pub fn env_prefix(&mut self, prefix: impl Into<String>) -> &mut Probe {
+        self.env_prefix = prefix.into();
+        self
+    }
+
+    /// falls back to the pkg-config package `name` instead of the one named in the environment
+    pub fn pkg_config_name(&mut self, name: impl Into<String>) -> &mut Probe {
+        self.pkg_config_name = Some(name.into());
+        self
+    }
+
+    /// treats the installation as `implementation` unless `<prefix>_IMPL` names another one
+    ///
+    /// This picks the pkg-config package to fall back to and overrides the guess from the wrapper
+    /// output.
+    pub fn implementation(&mut self, implementation: Implementation) -> &mut Probe {
+        self.implementation = Some(implementation);
+        self
+    }
+
+    /// whether to print the `cargo:` lines a build script needs, on by default
+    ///
+    /// Outside a build script they are noise, with this off warnings go to stderr instead and the
+    /// `rerun-if` lines are left out.
+    pub fn cargo_metadata(&mut self, cargo_metadata: bool) -> &mut Probe {
+        self.cargo_metadata = cargo_metadata;
+        self
+    }
+
+    /// the variable `<prefix>_<name>`
+    fn var(&self, name: &str) -> String {
+        format!("{}_{}", self.env_prefix, name)
+    }
+
+    /// name of the compiler wrapper, taken from `OSHMEM_CC_<target>`, `OSHMEM_CC` or `OSHCC` if
+    /// set
+    ///
+    /// When cross-compiling only the target specific variable is consulted, since the wrapper
+    /// found on `PATH` describes the host installation. `None` means no usable wrapper is
+    /// configured.
+    fn oshcc(&self, target: &str, host: &str) -> Option<String> {
+        if let Some(wrapper) = &self.wrapper {
+            return Some(wrapper.clone());
+        }
+
+        // Accept both `OSHMEM_CC_aarch64-unknown-linux-gnu` and
+        // `OSHMEM_CC_aarch64_unknown_linux_gnu`, like the `cc` crate does for `CC_<target>`.
+        let target_vars = [
+            format!("OSHMEM_CC_{}", target),
+            format!("OSHMEM_CC_{}", target.replace('-', "_")),
+        ];
+        for var in &target_vars {
+            emit(self.cargo_metadata, "rerun-if-env-changed", var);
+        }
+        emit(self.cargo_metadata, "rerun-if-env-changed", "OSHMEM_CC");
+        emit(self.cargo_metadata, "rerun-if-env-changed", "OSHCC");
+
+        if let Some(oshcc) = target_vars.iter().find_map(|var| env::var(var).ok()) {
+            return Some(oshcc);
+        }
+        if target != host {
+            return None;
+        }
+
+        Some(
+            env::var("OSHMEM_CC")
+                .or_else(|_| env::var("OSHCC"))
+                .unwrap_or_else(|_| String::from("oshcc")),
+        )
+    }
+
+    /// the compiler from `CC_<target>`, tried as a wrapper when no OpenSHMEM wrapper is found
+    ///
+    /// Cross builds often name the target compiler this way, like the `cc` crate reads it, and
+    /// on some sites that compiler is the OpenSHMEM wrapper for the target.
+    fn cc_from_env(&self, target: &str) -> Option<String> {
+        let vars = [
+            format!("CC_{}", target),
+            format!("CC_{}", target.replace('-', "_")),
+        ];
+        for var in &vars {
+            emit(self.cargo_metadata, "rerun-if-env-changed", var);
+        }
+        vars.iter().find_map(|var| env::var(var).ok())
+    }
+
+    /// name of the pkg-config package, taken from `OSHMEM_PKG_CONFIG` if set
+    fn package(&self, requested: Option<Implementation>) -> String {
+        if let Some(name) = &self.pkg_config_name {
+            return name.clone();
+        }
+        emit(
+            self.cargo_metadata,
+            "rerun-if-env-changed",
+            "OSHMEM_PKG_CONFIG",
+        );
+
+        env::var("OSHMEM_PKG_CONFIG").unwrap_or_else(|_| {
+            let implementation = requested.unwrap_or(Implementation::OpenMpi);
+            String::from(implementation.pkg_config_name())
+        })
+    }
+
+    /// probes the wrapper, falling back to pkg-config
+    fn probe_via_wrapper(
+        &self,
+        target: &str,
+        host: &str,
+        requested: Option<Implementation>,
+        env_script: Option<&Path>,
+    ) -> Result<Library, ProbeError> {
+        let oshcc = self.oshcc(target, host);
+        let probed = match &oshcc {
+            Some(oshcc) => probe_via_oshcc(oshcc, env_script, self.cargo_metadata),
+            None => {
+                emit(
+                    self.cargo_metadata,
+                    "warning",
+                    format_args!(
+                        "cross-compiling for {}, not running the host `oshcc`; set OSHMEM_CC_{} to use a wrapper for the target",
+                        target, target
+                    ),
+                );
+                Err(ProbeError::WrapperNotFound)
+            }
+        };
+        let probed = match (probed, self.cc_from_env(target)) {
+            (Err(ProbeError::WrapperNotFound), Some(cc)) => {
+                probe_via_oshcc(&cc, env_script, self.cargo_metadata).map_err(|err| {
+                    emit(
+                        self.cargo_metadata,
+                        "warning",
+                        format_args!(
+                            "`{}` from CC_{} doesn't work as an OpenSHMEM wrapper: {}",
+                            cc, target, err
+                        ),
+                    );
+                    ProbeError::WrapperNotFound
+                })
+            }
+            (probed, _) => probed,
+        };
+        match probed {
+            // The wrapper isn't installed, see if pkg-config knows about the library instead.
+            Err(ProbeError::WrapperNotFound) => {
+                let package = self.package(requested);
+                probe_via_pkg_config(&package, self.cargo_metadata).map_err(|err| {
+                    ProbeError::NotFound {
+                        wrapper: oshcc.unwrap_or_else(|| String::from("oshcc")),
+                        package,
+                        err: Box::new(err),
+                    }
+                })
+            }
+            // pkg-config may know libraries the wrapper leaves out.
+            Ok(mut lib) => {
+                if let Ok(extra) =
+                    probe_via_pkg_config(&self.package(requested), self.cargo_metadata)
+                {
+                    merge_library(&mut lib, extra);
+                }
+                Ok(lib)
+            }
+            probed => probed,
+        }
+    }
+
+    /// looks for the OpenSHMEM installation and the spec version it implements
+    ///
+    /// This prints `cargo:` lines unless [`cargo_metadata`](Probe::cargo_metadata) is off, so it
+    /// is meant to be called from a build script. It reads `TARGET` and `HOST` to tell whether
+    /// the build is cross-compiling.
+    pub fn probe(&self) -> Result<Library, ProbeError> {
+        let impl_var = self.var("IMPL");
+        emit(self.cargo_metadata, "rerun-if-env-changed", "OPENSHMEM_DIR");
+        emit(
+            self.cargo_metadata,
+            "rerun-if-env-changed",
+            "CRAY_OPENSHMEMX_DIR",
+        );
+        emit(self.cargo_metadata, "rerun-if-env-changed", &impl_var);
+
+        let host = env::var("HOST").unwrap_or_default();
+        let target = env::var("TARGET").unwrap_or_else(|_| host.clone());
+        let requested = match env::var(&impl_var) {
+            Ok(name) => Some(
+                Implementation::from_name(&name).ok_or(ProbeError::UnknownImplementation(name))?,
+            ),
+            Err(_) => self.implementation,
+        };
+        let env_script_var = self.var("ENV_SCRIPT");
+        emit(self.cargo_metadata, "rerun-if-env-changed", &env_script_var);
+        let env_script = env::var_os(&env_script_var).map(PathBuf::from);
+        if let Some(script) = &env_script {
+            emit(self.cargo_metadata, "rerun-if-changed", script.display());
+        }
+        let cray_dir = env::var_os("CRAY_OPENSHMEMX_DIR");
+        let openshmem_dir = env::var_os("OPENSHMEM_DIR");
+
+        let (mut oshmem, prefix) = if let Some(prefix) = &openshmem_dir {
+            let implementation = requested.unwrap_or(Implementation::Unknown);
+            (
+                probe_via_prefix(Path::new(prefix), implementation),
+                Some(prefix),
+            )
+        } else if requested == Some(Implementation::Cray)
+            || (requested.is_none() && cray_dir.is_some())
+        {
+            // Cray systems have no `oshcc`, the module only points us at the install.
+            let prefix = cray_dir.as_ref().ok_or(ProbeError::CrayDirNotSet)?;
+            (
+                probe_via_prefix(Path::new(prefix), Implementation::Cray),
+                Some(prefix),
+            )
+        } else {
+            (
+                self.probe_via_wrapper(&target, &host, requested, env_script.as_deref())?,
+                None,
+            )
+        };
+        let prefixes = install_prefixes(&oshmem, prefix.map(Path::new), self.cargo_metadata);
+        add_prefix_include_dirs(&mut oshmem, &prefixes);
+        if let Some(implementation) = requested {
+            if oshmem.implementation != Implementation::Unknown
+                && oshmem.implementation != implementation
+            {
+                emit(
+                    self.cargo_metadata,
+                    "warning",
+                    format_args!(
+                        "building for {} but the installation looks like {}",
+                        implementation.name(),
+                        oshmem.implementation.name()
+                    ),
+                );
+            }
+            oshmem.implementation = implementation;
+        }
+        if oshmem.implementation == Implementation::OpenMpi {
+            add_open_mpi_libs(&mut oshmem);
+        }
+        // The search paths still come from the probe, only the link line is replaced.
+        let libs_var = self.var("LIBS");
+        emit(self.cargo_metadata, "rerun-if-env-changed", &libs_var);
+        if let Ok(libs) = env::var(&libs_var) {
+            oshmem.libs = libs
+                .split(':')
+                .filter(|lib| !lib.is_empty())
+                .map(str::to_owned)
+                .collect();
+            oshmem.static_libs.retain(|lib| oshmem.libs.contains(lib));
+        }
+
+        oshmem.env_script = env_script.clone();
+        if let Some(version) = detect_version(&oshmem, env_script.as_deref()) {
+            oshmem.version = version;
+        }
+        if let Some(oshcc) = &oshmem.oshcc {
+            report_wrapper(
+                oshcc,
+                &oshmem.version,
+                env_script.as_deref(),
+                self.cargo_metadata,
+            );
+        }
+        Ok(oshmem)
+    }
+}
+
+/// probes for the OpenSHMEM installation with the default [`Probe`] settings
+pub fn probe() -> Result<Library, ProbeError> {
+    Probe::new().probe()
+}
+
+/// replaces every occurrence of the directories in `dirs` in generated `code` by the matching
+/// placeholder, e.g. `OUT_DIR` by `$OUT_DIR`
+///
+/// Generated code that names the directory it was built in differs between machines. This makes
+/// it the same for the same input wherever it is built, for reproducible builds. Nested
+/// directories are fine, the longest match is replaced first.
+pub fn strip_build_paths(code: &str, dirs: &[(&Path, &str)]) -> String {
+    let mut dirs: Vec<_> = dirs
+        .iter()
+        .map(|(dir, placeholder)| (dir.display().to_string(), *placeholder))
+        .filter(|(dir, _)| !dir.is_empty())
+        .collect();
+    dirs.sort_by_key(|(dir, _)| std::cmp::Reverse(dir.len()));
+
+    let mut code = code.to_owned();
+    for (dir, placeholder) in &dirs {
+        code = code.replace(dir.as_str(), placeholder);
+    }
+    code
+}
@@ -0,0 +1,87 @@
+//! Runs the probe with a wrapper that only works after sourcing an environment script.
+//!
+//! This sets `<prefix>_ENV_SCRIPT`, so it lives in its own test binary.
+
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+use openshmem_build::{Probe, ProbeError};
+
+#[test]
+fn sources_env_script() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("sources_env_script");
+    fs::create_dir_all(&dir).unwrap();
+    let oshcc = dir.join("oshcc");
+    fs::write(
+        &oshcc,
+        "#!/bin/sh\n[ -n \"$SHMEM_MODULE\" ] || exit 1\necho \"gcc -L$SHMEM_MODULE/lib -lsma\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&oshcc, fs::Permissions::from_mode(0o755)).unwrap();
+    let script = dir.join("env.sh");
+    fs::write(&script, "echo loading >&2\nexport SHMEM_MODULE=/opt/sos\n").unwrap();
+    let broken = dir.join("broken.sh");
+    fs::write(&broken, "echo 'module: command not found' >&2\nfalse\n").unwrap();
+
+    unsafe { std::env::set_var("ENV_SCRIPT_TEST_ENV_SCRIPT", &script) };
+    let lib = Probe::new()
+        .wrapper(oshcc.to_str().unwrap())
+        .env_prefix("ENV_SCRIPT_TEST")
+        .probe()
+        .unwrap();
+    assert_eq!(lib.libs, ["sma"]);
+    assert_eq!(lib.lib_paths, [PathBuf::from("/opt/sos/lib")]);
+
+    // The module also puts `oshinfo` on `PATH`, which only the script's environment can find.
+    let bin = dir.join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    let oshinfo = bin.join("oshinfo");
+    fs::write(&oshinfo, "#!/bin/sh\necho 'OpenSHMEM spec version: 1.4'\n").unwrap();
+    fs::set_permissions(&oshinfo, fs::Permissions::from_mode(0o755)).unwrap();
+    let module = dir.join("module.sh");
+    fs::write(
+        &module,
+        format!(
+            "export SHMEM_MODULE=/opt/sos\nexport PATH=\"{}:$PATH\"\n",
+            bin.display()
+        ),
+    )
+    .unwrap();
+    unsafe { std::env::set_var("ENV_SCRIPT_TEST_ENV_SCRIPT", &module) };
+    let lib = Probe::new()
+        .wrapper(oshcc.to_str().unwrap())
+        .env_prefix("ENV_SCRIPT_TEST")
+        .probe()
+        .unwrap();
+    assert_eq!(lib.version, "1.4");
+    assert_eq!(lib.env_script.as_deref(), Some(module.as_path()));
+
+    // A bare file name is relative to the working directory, not looked up on `PATH`.
+    std::env::set_current_dir(&dir).unwrap();
+    unsafe { std::env::set_var("ENV_SCRIPT_TEST_ENV_SCRIPT", "module.sh") };
+    let lib = Probe::new()
+        .wrapper(oshcc.to_str().unwrap())
+        .env_prefix("ENV_SCRIPT_TEST")
+        .probe()
+        .unwrap();
+    assert_eq!(lib.libs, ["sma"]);
+
+    unsafe { std::env::set_var("ENV_SCRIPT_TEST_ENV_SCRIPT", &broken) };
+    match Probe::new()
+        .wrapper(oshcc.to_str().unwrap())
+        .env_prefix("ENV_SCRIPT_TEST")
+        .probe()
+    {
+        Err(ProbeError::EnvScriptFailed { script, stderr, .. }) => {
+            assert_eq!(script, broken);
+            assert!(stderr.contains("module: command not found"), "{}", stderr);
+        }
+        other => panic!(
+            "expected the script to fail, got {:?}",
+            other.map(|lib| lib.libs)
+        ),
+    }
+}
@@ -0,0 +1,50 @@
+//! Runs the probe against a fake wrapper and a pkg-config file for the same installation.
+//!
+//! This sets `PKG_CONFIG_PATH`, so it lives in its own test binary.
+
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+use openshmem_build::Probe;
+
+#[test]
+fn merges_pkg_config_libs() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("merges_pkg_config_libs");
+    let prefix = dir.join("prefix");
+    fs::create_dir_all(prefix.join("lib/pkgconfig")).unwrap();
+    fs::create_dir_all(prefix.join("include")).unwrap();
+    fs::write(
+        prefix.join("lib/pkgconfig/oshmem-merge.pc"),
+        format!(
+            "prefix={}\nName: oshmem\nDescription: test\nVersion: 1.5\n\
+             Libs: -L${{prefix}}/lib -L${{prefix}}/ucx/lib -lsma -lucp\n\
+             Cflags: -I${{prefix}}/include\n",
+            prefix.display()
+        ),
+    )
+    .unwrap();
+    let oshcc = dir.join("oshcc");
+    fs::write(
+        &oshcc,
+        format!(
+            "#!/bin/sh\necho 'gcc -I{0}/include -L{0}/lib -lsma'\n",
+            prefix.display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&oshcc, fs::Permissions::from_mode(0o755)).unwrap();
+    unsafe { std::env::set_var("PKG_CONFIG_PATH", prefix.join("lib/pkgconfig")) };
+
+    let lib = Probe::new()
+        .wrapper(oshcc.to_str().unwrap())
+        .pkg_config_name("oshmem-merge")
+        .probe()
+        .unwrap();
+    assert_eq!(lib.oshcc.as_deref(), oshcc.to_str());
+    assert_eq!(lib.libs, ["sma", "ucp"]);
+    assert_eq!(lib.lib_paths, [prefix.join("lib"), prefix.join("ucx/lib")]);
+    assert_eq!(lib.include_paths, [prefix.join("include")]);
+}
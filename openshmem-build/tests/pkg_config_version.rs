@@ -0,0 +1,35 @@
+//! Runs the probe against a pkg-config file alone, with no wrapper installed.
+//!
+//! This sets `PKG_CONFIG_PATH`, so it lives in its own test binary.
+
+use std::fs;
+use std::path::PathBuf;
+
+use openshmem_build::Probe;
+
+#[test]
+fn package_version_is_not_the_spec_version() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("package_version");
+    let prefix = dir.join("prefix");
+    fs::create_dir_all(prefix.join("lib/pkgconfig")).unwrap();
+    fs::create_dir_all(prefix.join("include")).unwrap();
+    fs::write(
+        prefix.join("lib/pkgconfig/oshmem-version.pc"),
+        format!(
+            "prefix={}\nName: oshmem\nDescription: test\nVersion: 4.1.5\n\
+             Libs: -L${{prefix}}/lib -loshmem\nCflags: -I${{prefix}}/include\n",
+            prefix.display()
+        ),
+    )
+    .unwrap();
+    unsafe { std::env::set_var("PKG_CONFIG_PATH", prefix.join("lib/pkgconfig")) };
+
+    let lib = Probe::new()
+        .wrapper(dir.join("no-oshcc").to_str().unwrap())
+        .pkg_config_name("oshmem-version")
+        .probe()
+        .unwrap();
+    assert_eq!(lib.libs, ["oshmem"]);
+    assert_eq!(lib.version, "unknown");
+    assert_eq!(lib.spec_version(), None);
+}
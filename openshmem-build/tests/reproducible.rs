@@ -0,0 +1,84 @@
+//! Checks that generated code built in different directories is identical once the build
+//! paths are stripped.
+//!
+//! `bindings_in_two_checkouts` runs bindgen for real with the build script's configuration, so
+//! it needs the OpenSHMEM headers and libclang and is ignored by default. Run it with
+//! `cargo test -p openshmem-build -- --ignored`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use openshmem_build::{Library, strip_build_paths};
+
+#[path = "../../build/bindings.rs"]
+mod bindings;
+
+/// what bindgen emits for a header in `out_dir`, with the paths it may embed
+fn generated(manifest_dir: &str, out_dir: &str) -> String {
+    format!(
+        "/* automatically generated by rust-bindgen */\n\
+         // from {0}/wrapper.h\n\
+         pub const SHMEM_MAJOR_VERSION: i32 = 1;\n\
+         #[doc = \"see {1}/include/shmem.h\"]\n\
+         pub fn shmem_init();\n",
+        out_dir, manifest_dir
+    )
+}
+
+#[test]
+fn same_bindings_in_different_out_dirs() {
+    let builds = [
+        (
+            "/home/a/openshmem-sys",
+            "/home/a/openshmem-sys/target/debug/build/openshmem-sys-1/out",
+        ),
+        (
+            "/tmp/b/openshmem-sys",
+            "/tmp/b/openshmem-sys/target/release/build/openshmem-sys-2/out",
+        ),
+    ];
+    let [first, second] = builds.map(|(manifest_dir, out_dir)| {
+        strip_build_paths(
+            &generated(manifest_dir, out_dir),
+            &[
+                (Path::new(manifest_dir), "$CARGO_MANIFEST_DIR"),
+                (Path::new(out_dir), "$OUT_DIR"),
+            ],
+        )
+    });
+    assert_eq!(first, second);
+    assert!(first.contains("// from $OUT_DIR/wrapper.h"), "{}", first);
+    assert!(!first.contains("/home/a"), "{}", first);
+}
+
+/// generates the bindings of a checkout in `manifest_dir` into `out_dir` like a build with the
+/// `reproducible` feature, returning them
+fn bindings_in(manifest_dir: &Path, out_dir: &Path, oshmem: &Library) -> String {
+    fs::create_dir_all(manifest_dir.join("include")).unwrap();
+    fs::create_dir_all(out_dir).unwrap();
+    let header = manifest_dir.join("include/wrapper.h");
+    fs::copy(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../include/wrapper.h"),
+        &header,
+    )
+    .unwrap();
+    let out_file = out_dir.join("bindings.rs");
+    bindings::builder(oshmem, &header)
+        .generate()
+        .expect("bindgen failed on the installed headers")
+        .write_to_file(&out_file)
+        .unwrap();
+    bindings::strip_bindings_paths(&out_file, out_dir, manifest_dir);
+    fs::read_to_string(out_file).unwrap()
+}
+
+#[test]
+#[ignore = "needs the OpenSHMEM headers and libclang"]
+fn bindings_in_two_checkouts() {
+    let oshmem = openshmem_build::probe().expect("no OpenSHMEM installation to generate from");
+    let tmp = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("reproducible");
+    let first = bindings_in(&tmp.join("a"), &tmp.join("a/target/out"), &oshmem);
+    let second = bindings_in(&tmp.join("b/src"), &tmp.join("b/build/out"), &oshmem);
+    assert!(first.contains("shmem_init"), "{}", first);
+    assert_eq!(first, second);
+}
@@ -0,0 +1,55 @@
+//! Reads site configuration files instead of probing.
+
+use std::fs;
+use std::path::PathBuf;
+
+use openshmem_build::{Implementation, Library, ProbeError};
+
+fn write_config(name: &str, text: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("openshmem-sys.toml");
+    fs::write(&path, text).unwrap();
+    path
+}
+
+#[test]
+fn reads_site_config() {
+    let path = write_config(
+        "reads_site_config",
+        "# site configuration\n\
+         libs = [\"sma\", \"pmi_simple\"]\n\
+         lib_paths = [\"/opt/sos/lib\", \"/opt/my libs\"] # with a space\n\
+         include_paths = [\"/opt/sos/include\"]\n\
+         implementation = \"sandia\"\n\
+         version = \"1.5\"\n",
+    );
+    let oshmem = Library::from_site_config(&path).unwrap();
+    assert_eq!(oshmem.oshcc, None);
+    assert_eq!(oshmem.libs, ["sma", "pmi_simple"]);
+    assert_eq!(
+        oshmem.lib_paths,
+        [PathBuf::from("/opt/sos/lib"), PathBuf::from("/opt/my libs")]
+    );
+    assert_eq!(oshmem.include_paths, [PathBuf::from("/opt/sos/include")]);
+    assert_eq!(oshmem.implementation, Implementation::Sandia);
+    assert_eq!(oshmem.spec_version(), Some((1, 5)));
+}
+
+#[test]
+fn rejects_unknown_key() {
+    let path = write_config(
+        "rejects_unknown_key",
+        "libs = [\"oshmem\"]\nlibdir = \"/opt/lib\"\n",
+    );
+    match Library::from_site_config(&path) {
+        Err(ProbeError::SiteConfig { line, message, .. }) => {
+            assert_eq!(line, 2);
+            assert!(message.contains("libdir"), "{}", message);
+        }
+        other => panic!(
+            "expected a site config error, got {:?}",
+            other.map(|lib| lib.libs)
+        ),
+    }
+}
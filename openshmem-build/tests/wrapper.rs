@@ -0,0 +1,148 @@
+//! Runs the probe against fake compiler wrappers.
+
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::process::Command;
+
+use openshmem_build::{Implementation, Probe, ProbeError};
+
+/// writes an executable shell script named `name` with `body` into a fresh directory
+fn fake_wrapper(name: &str, body: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("oshcc");
+    fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+#[test]
+fn show_on_stderr() {
+    let oshcc = fake_wrapper(
+        "show_on_stderr",
+        "echo 'gcc -I/opt/shmem/include -L/opt/shmem/lib -lsma' >&2",
+    );
+
+    let lib = Probe::new()
+        .wrapper(oshcc.to_str().unwrap())
+        .probe()
+        .unwrap();
+    assert_eq!(lib.libs, ["sma"]);
+    assert_eq!(lib.lib_paths, [PathBuf::from("/opt/shmem/lib")]);
+    assert_eq!(lib.include_paths, [PathBuf::from("/opt/shmem/include")]);
+}
+
+#[test]
+fn include_subdir_of_prefix() {
+    let prefix = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("include_subdir_prefix");
+    fs::create_dir_all(prefix.join("include/shmem")).unwrap();
+    fs::create_dir_all(prefix.join("lib")).unwrap();
+    fs::write(prefix.join("include/shmem/shmem.h"), "").unwrap();
+    let oshcc = fake_wrapper(
+        "include_subdir_of_prefix",
+        &format!("echo 'gcc -I{0}/include -L{0}/lib -lsma'", prefix.display()),
+    );
+
+    let lib = Probe::new()
+        .wrapper(oshcc.to_str().unwrap())
+        .probe()
+        .unwrap();
+    assert_eq!(
+        lib.include_paths,
+        [prefix.join("include"), prefix.join("include/shmem")]
+    );
+}
+
+#[test]
+fn open_mpi_adds_libmpi() {
+    let prefix = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("open_mpi_prefix");
+    fs::create_dir_all(prefix.join("lib")).unwrap();
+    fs::write(prefix.join("lib/liboshmem.so"), "").unwrap();
+    fs::write(prefix.join("lib/libmpi.so"), "").unwrap();
+    let oshcc = fake_wrapper(
+        "open_mpi_adds_libmpi",
+        &format!("echo 'gcc -L{}/lib -loshmem'", prefix.display()),
+    );
+
+    let lib = Probe::new()
+        .wrapper(oshcc.to_str().unwrap())
+        .probe()
+        .unwrap();
+    assert_eq!(lib.implementation, Implementation::OpenMpi);
+    assert_eq!(lib.libs, ["oshmem", "mpi"]);
+}
+
+#[test]
+fn unbalanced_quote_is_an_error() {
+    let oshcc = fake_wrapper(
+        "unbalanced_quote_is_an_error",
+        "case \"$1\" in --showme*) exit 1 ;; esac\necho 'gcc -I\"/opt/my include -lsma'",
+    );
+
+    match Probe::new().wrapper(oshcc.to_str().unwrap()).probe() {
+        Err(ProbeError::SplitFailed { line, .. }) => {
+            assert_eq!(line, "gcc -I\"/opt/my include -lsma");
+        }
+        other => panic!(
+            "expected a split error, got {:?}",
+            other.map(|lib| lib.libs)
+        ),
+    }
+}
+
+#[test]
+fn unbalanced_quotes_dont_pair_across_queries() {
+    let oshcc = fake_wrapper(
+        "unbalanced_quotes_dont_pair_across_queries",
+        "case \"$1\" in --showme:*) echo 'gcc -I\"/opt/my include -lsma' ;; *) echo gcc ;; esac",
+    );
+
+    match Probe::new().wrapper(oshcc.to_str().unwrap()).probe() {
+        Err(ProbeError::SplitFailed { line, .. }) => {
+            assert_eq!(line, "gcc -I\"/opt/my include -lsma");
+        }
+        other => panic!(
+            "expected a split error, got {:?}",
+            other.map(|lib| lib.include_paths)
+        ),
+    }
+}
+
+#[test]
+fn paths_with_spaces() {
+    let oshcc = fake_wrapper(
+        "paths_with_spaces",
+        "echo \"gcc -I\\\"/opt/my include\\\" -L'/opt/my lib' -lsma\"",
+    );
+
+    let lib = Probe::new()
+        .wrapper(oshcc.to_str().unwrap())
+        .probe()
+        .unwrap();
+    assert_eq!(lib.include_paths, [PathBuf::from("/opt/my include")]);
+    assert_eq!(lib.lib_paths, [PathBuf::from("/opt/my lib")]);
+    assert_eq!(lib.libs, ["sma"]);
+}
+
+#[test]
+fn probe_binary_prints_no_cargo_lines() {
+    let oshcc = fake_wrapper(
+        "probe_binary_prints_no_cargo_lines",
+        "echo 'gcc -I/opt/shmem/include -L/opt/shmem/lib -lsma'",
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_openshmem-probe"))
+        .env("OSHMEM_CC", &oshcc)
+        .env_remove("OPENSHMEM_LIB_DIR")
+        .env_remove("OPENSHMEM_DIR")
+        .env_remove("OPENSHMEM_SYS_CONFIG")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("sma"), "{}", stdout);
+    assert!(!stdout.contains("cargo:"), "{}", stdout);
+}
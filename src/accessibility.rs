@@ -0,0 +1,50 @@
+//! Queries for which PEs and addresses can be reached, over `shmem_pe_accessible`,
+//! `shmem_addr_accessible` and `shmem_ptr`.
+
+use std::ffi::c_void;
+
+/// whether PE `pe` runs the same executable and can be the target of OpenSHMEM operations
+pub fn pe_accessible(pe: i32) -> bool {
+    unsafe { crate::shmem_pe_accessible(pe) != 0 }
+}
+
+/// whether `addr` is symmetric, i.e. names a remotely accessible object on PE `pe`
+// The library only looks at the address, it never reads through it.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn addr_accessible(addr: *const c_void, pe: i32) -> bool {
+    unsafe { crate::shmem_addr_accessible(addr, pe) != 0 }
+}
+
+/// the address of the symmetric object `local` on PE `pe` for direct loads and stores
+///
+/// `None` if PE `pe` isn't reachable that way, e.g. because it runs on another node. Accesses
+/// through the pointer are ordinary memory accesses racing with everything else that touches the
+/// object, the OpenSHMEM ordering routines don't cover them.
+pub fn remote_ptr<T>(local: *const T, pe: i32) -> Option<*mut T> {
+    let ptr = unsafe { crate::shmem_ptr(local as *const c_void, pe) };
+    (!ptr.is_null()).then_some(ptr as *mut T)
+}
+
+/// a view of PE `pe`'s copy of the symmetric slice `local`, read with ordinary loads
+///
+/// `None` if PE `pe` isn't reachable through `shmem_ptr`, e.g. because it runs on another node,
+/// or if `local` isn't symmetric. The view has the same length as `local` and borrows it, so the
+/// local copy can't be changed through this PE's safe handles while the view lives. `T: Copy`
+/// keeps the view to plain data, there is no peer-owned value to drop or move out.
+///
+/// # Safety
+///
+/// Nothing stops *other* PEs from changing the memory behind the view, so the caller has to:
+///
+/// - Make sure no PE (including `pe` itself) puts to, atomically updates or stores to the object
+///   while the view lives, e.g. by only holding it between two barriers. A write racing with a
+///   read through the view is a data race.
+/// - Order its reads with a barrier or a signal the peer sets after its writes complete.
+///   `quiet`, `fence` and the other ordering routines don't order loads through the view.
+/// - Keep the object alive on every PE while any of them holds a view of it, in particular the
+///   collective free of a [`SymmetricBox`](crate::mem::SymmetricBox) or
+///   [`SymmetricVec`](crate::mem::SymmetricVec) must not happen before every view is dropped.
+pub unsafe fn local_peer_slice<T: Copy>(local: &[T], pe: i32) -> Option<&[T]> {
+    let ptr = remote_ptr(local.as_ptr(), pe)?;
+    Some(unsafe { std::slice::from_raw_parts(ptr as *const T, local.len()) })
+}
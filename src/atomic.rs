@@ -0,0 +1,128 @@
+//! Typed atomic memory operations over the `shmem_<type>_atomic_*` routines.
+
+use std::os::raw::{c_int, c_longlong, c_uint, c_ulonglong};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Integer types with OpenSHMEM atomic operations
+///
+/// This is sealed, the library only provides the routines for a fixed set of types.
+pub trait ShmemAtomic: Copy + private::Sealed {
+    #[doc(hidden)]
+    unsafe fn atomic_add(dest: *mut Self, value: Self, pe: i32);
+    #[doc(hidden)]
+    unsafe fn atomic_fetch(source: *const Self, pe: i32) -> Self;
+    #[doc(hidden)]
+    unsafe fn atomic_compare_swap(dest: *mut Self, cond: Self, value: Self, pe: i32) -> Self;
+    #[doc(hidden)]
+    unsafe fn atomic_swap(dest: *mut Self, value: Self, pe: i32) -> Self;
+    #[doc(hidden)]
+    unsafe fn atomic_fetch_add(dest: *mut Self, value: Self, pe: i32) -> Self;
+}
+
+macro_rules! impl_atomic {
+    ($t:ty, $c:ty, $add:ident, $fetch:ident, $compare_swap:ident, $swap:ident, $fetch_add:ident) => {
+        impl private::Sealed for $t {}
+
+        impl ShmemAtomic for $t {
+            unsafe fn atomic_add(dest: *mut Self, value: Self, pe: i32) {
+                unsafe { crate::$add(dest as *mut $c, value as $c, pe) }
+            }
+
+            unsafe fn atomic_fetch(source: *const Self, pe: i32) -> Self {
+                unsafe { crate::$fetch(source as *const $c, pe) as $t }
+            }
+
+            unsafe fn atomic_compare_swap(
+                dest: *mut Self,
+                cond: Self,
+                value: Self,
+                pe: i32,
+            ) -> Self {
+                unsafe { crate::$compare_swap(dest as *mut $c, cond as $c, value as $c, pe) as $t }
+            }
+
+            unsafe fn atomic_swap(dest: *mut Self, value: Self, pe: i32) -> Self {
+                unsafe { crate::$swap(dest as *mut $c, value as $c, pe) as $t }
+            }
+
+            unsafe fn atomic_fetch_add(dest: *mut Self, value: Self, pe: i32) -> Self {
+                unsafe { crate::$fetch_add(dest as *mut $c, value as $c, pe) as $t }
+            }
+        }
+    };
+}
+
+impl_atomic!(
+    i32,
+    c_int,
+    shmem_int_atomic_add,
+    shmem_int_atomic_fetch,
+    shmem_int_atomic_compare_swap,
+    shmem_int_atomic_swap,
+    shmem_int_atomic_fetch_add
+);
+impl_atomic!(
+    u32,
+    c_uint,
+    shmem_uint_atomic_add,
+    shmem_uint_atomic_fetch,
+    shmem_uint_atomic_compare_swap,
+    shmem_uint_atomic_swap,
+    shmem_uint_atomic_fetch_add
+);
+impl_atomic!(
+    i64,
+    c_longlong,
+    shmem_longlong_atomic_add,
+    shmem_longlong_atomic_fetch,
+    shmem_longlong_atomic_compare_swap,
+    shmem_longlong_atomic_swap,
+    shmem_longlong_atomic_fetch_add
+);
+impl_atomic!(
+    u64,
+    c_ulonglong,
+    shmem_ulonglong_atomic_add,
+    shmem_ulonglong_atomic_fetch,
+    shmem_ulonglong_atomic_compare_swap,
+    shmem_ulonglong_atomic_swap,
+    shmem_ulonglong_atomic_fetch_add
+);
+
+/// atomically adds `value` to the symmetric `dest` on PE `pe`
+pub fn atomic_add<T: ShmemAtomic>(dest: &mut T, value: T, pe: i32) {
+    unsafe { T::atomic_add(dest, value, pe) }
+}
+
+/// atomically reads the symmetric `source` on PE `pe`
+pub fn atomic_fetch<T: ShmemAtomic>(source: &T, pe: i32) -> T {
+    unsafe { T::atomic_fetch(source, pe) }
+}
+
+/// atomically replaces the symmetric `dest` on PE `pe` with `value` if it equals `cond`,
+/// returning the value it held before
+pub fn atomic_compare_swap<T: ShmemAtomic>(dest: &mut T, cond: T, value: T, pe: i32) -> T {
+    unsafe { T::atomic_compare_swap(dest, cond, value, pe) }
+}
+
+/// atomically replaces the symmetric `target` on PE `pe` with `value` if it equals `cond`
+///
+/// Returns the value `target` held before, which equals `cond` exactly when the swap happened.
+/// This is [`atomic_compare_swap`] under the name the lock-free algorithms use.
+pub fn compare_swap<T: ShmemAtomic>(target: &mut T, cond: T, value: T, pe: i32) -> T {
+    atomic_compare_swap(target, cond, value, pe)
+}
+
+/// atomically replaces the symmetric `dest` on PE `pe` with `value`, returning the value it held
+/// before
+pub fn atomic_swap<T: ShmemAtomic>(dest: &mut T, value: T, pe: i32) -> T {
+    unsafe { T::atomic_swap(dest, value, pe) }
+}
+
+/// atomically adds `value` to the symmetric `dest` on PE `pe`, returning the value it held before
+pub fn atomic_fetch_add<T: ShmemAtomic>(dest: &mut T, value: T, pe: i32) -> T {
+    unsafe { T::atomic_fetch_add(dest, value, pe) }
+}
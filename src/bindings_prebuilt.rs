@@ -0,0 +1,1162 @@
+// Pregenerated bindings used by the `vendored-bindings` feature.
+//
+// This is a curated subset of the OpenSHMEM 1.5 C API for x86_64/aarch64 Linux, laid out the way
+// bindgen emits it. To refresh it, build without the feature on a machine with `oshcc` and copy
+// the relevant items from `$OUT_DIR/bindings.rs`. Constant values follow Sandia OpenSHMEM.
+
+pub type ptrdiff_t = isize;
+pub const SHMEM_MAJOR_VERSION: i32 = 1;
+pub const SHMEM_MINOR_VERSION: i32 = 5;
+pub const SHMEM_MAX_NAME_LEN: i32 = 256;
+pub const SHMEM_THREAD_SINGLE: i32 = 0;
+pub const SHMEM_THREAD_FUNNELED: i32 = 1;
+pub const SHMEM_THREAD_SERIALIZED: i32 = 2;
+pub const SHMEM_THREAD_MULTIPLE: i32 = 3;
+pub const SHMEM_CMP_EQ: i32 = 1;
+pub const SHMEM_CMP_NE: i32 = 2;
+pub const SHMEM_CMP_GT: i32 = 3;
+pub const SHMEM_CMP_GE: i32 = 4;
+pub const SHMEM_CMP_LT: i32 = 5;
+pub const SHMEM_CMP_LE: i32 = 6;
+pub const SHMEM_SIGNAL_SET: i32 = 0;
+pub const SHMEM_SIGNAL_ADD: i32 = 1;
+pub const SHMEM_MALLOC_ATOMICS_REMOTE: i32 = 1;
+pub const SHMEM_MALLOC_SIGNAL_REMOTE: i32 = 2;
+pub const SHMEM_CTX_SERIALIZED: i32 = 1;
+pub const SHMEM_CTX_PRIVATE: i32 = 2;
+pub const SHMEM_CTX_NOSTORE: i32 = 4;
+pub type shmem_ctx_t = *mut ::std::os::raw::c_void;
+unsafe extern "C" {
+    pub static mut SHMEM_CTX_DEFAULT: shmem_ctx_t;
+}
+pub const SHMEM_TEAM_NUM_CONTEXTS: i32 = 1;
+pub type shmem_team_t = *mut ::std::os::raw::c_void;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct shmem_team_config_t {
+    pub num_contexts: ::std::os::raw::c_int,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of shmem_team_config_t"][::std::mem::size_of::<shmem_team_config_t>() - 4usize];
+    ["Alignment of shmem_team_config_t"][::std::mem::align_of::<shmem_team_config_t>() - 4usize];
+    ["Offset of field: shmem_team_config_t::num_contexts"]
+        [::std::mem::offset_of!(shmem_team_config_t, num_contexts) - 0usize];
+};
+unsafe extern "C" {
+    pub static mut SHMEM_TEAM_WORLD: shmem_team_t;
+}
+unsafe extern "C" {
+    pub static mut SHMEM_TEAM_SHARED: shmem_team_t;
+}
+unsafe extern "C" {
+    pub fn shmem_init();
+}
+unsafe extern "C" {
+    pub fn shmem_finalize();
+}
+unsafe extern "C" {
+    pub fn shmem_init_thread(
+        requested: ::std::os::raw::c_int,
+        provided: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_query_thread(provided: *mut ::std::os::raw::c_int);
+}
+unsafe extern "C" {
+    pub fn shmem_global_exit(status: ::std::os::raw::c_int);
+}
+unsafe extern "C" {
+    pub fn shmem_my_pe() -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_n_pes() -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_info_get_version(
+        major: *mut ::std::os::raw::c_int,
+        minor: *mut ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_info_get_name(name: *mut ::std::os::raw::c_char);
+}
+unsafe extern "C" {
+    pub fn shmem_pe_accessible(pe: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_addr_accessible(
+        addr: *const ::std::os::raw::c_void,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_ptr(
+        dest: *const ::std::os::raw::c_void,
+        pe: ::std::os::raw::c_int,
+    ) -> *mut ::std::os::raw::c_void;
+}
+unsafe extern "C" {
+    pub fn shmem_malloc(size: usize) -> *mut ::std::os::raw::c_void;
+}
+unsafe extern "C" {
+    pub fn shmem_calloc(count: usize, size: usize) -> *mut ::std::os::raw::c_void;
+}
+unsafe extern "C" {
+    pub fn shmem_align(alignment: usize, size: usize) -> *mut ::std::os::raw::c_void;
+}
+unsafe extern "C" {
+    pub fn shmem_malloc_with_hints(
+        size: usize,
+        hints: ::std::os::raw::c_long,
+    ) -> *mut ::std::os::raw::c_void;
+}
+unsafe extern "C" {
+    pub fn shmem_free(ptr: *mut ::std::os::raw::c_void);
+}
+unsafe extern "C" {
+    pub fn shmem_barrier_all();
+}
+unsafe extern "C" {
+    pub fn shmem_set_lock(lock: *mut ::std::os::raw::c_long);
+}
+unsafe extern "C" {
+    pub fn shmem_clear_lock(lock: *mut ::std::os::raw::c_long);
+}
+unsafe extern "C" {
+    pub fn shmem_test_lock(lock: *mut ::std::os::raw::c_long) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_sync_all();
+}
+unsafe extern "C" {
+    pub fn shmem_fence();
+}
+unsafe extern "C" {
+    pub fn shmem_quiet();
+}
+unsafe extern "C" {
+    pub fn shmem_putmem(
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_getmem(
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_putmem_nbi(
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_getmem_nbi(
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_int_p(
+        dest: *mut ::std::os::raw::c_int,
+        value: ::std::os::raw::c_int,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_int_g(
+        source: *const ::std::os::raw::c_int,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_p(
+        dest: *mut ::std::os::raw::c_uint,
+        value: ::std::os::raw::c_uint,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_uint_g(
+        source: *const ::std::os::raw::c_uint,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_uint;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_p(
+        dest: *mut ::std::os::raw::c_longlong,
+        value: ::std::os::raw::c_longlong,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_g(
+        source: *const ::std::os::raw::c_longlong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_longlong;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_p(
+        dest: *mut ::std::os::raw::c_ulonglong,
+        value: ::std::os::raw::c_ulonglong,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_g(
+        source: *const ::std::os::raw::c_ulonglong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shmem_float_p(dest: *mut f32, value: f32, pe: ::std::os::raw::c_int);
+}
+unsafe extern "C" {
+    pub fn shmem_float_g(source: *const f32, pe: ::std::os::raw::c_int) -> f32;
+}
+unsafe extern "C" {
+    pub fn shmem_double_p(dest: *mut f64, value: f64, pe: ::std::os::raw::c_int);
+}
+unsafe extern "C" {
+    pub fn shmem_double_g(source: *const f64, pe: ::std::os::raw::c_int) -> f64;
+}
+unsafe extern "C" {
+    pub fn shmem_int_iput(
+        dest: *mut ::std::os::raw::c_int,
+        source: *const ::std::os::raw::c_int,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_int_iget(
+        dest: *mut ::std::os::raw::c_int,
+        source: *const ::std::os::raw::c_int,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_uint_iput(
+        dest: *mut ::std::os::raw::c_uint,
+        source: *const ::std::os::raw::c_uint,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_uint_iget(
+        dest: *mut ::std::os::raw::c_uint,
+        source: *const ::std::os::raw::c_uint,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_iput(
+        dest: *mut ::std::os::raw::c_longlong,
+        source: *const ::std::os::raw::c_longlong,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_iget(
+        dest: *mut ::std::os::raw::c_longlong,
+        source: *const ::std::os::raw::c_longlong,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_iput(
+        dest: *mut ::std::os::raw::c_ulonglong,
+        source: *const ::std::os::raw::c_ulonglong,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_iget(
+        dest: *mut ::std::os::raw::c_ulonglong,
+        source: *const ::std::os::raw::c_ulonglong,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_float_iput(
+        dest: *mut f32,
+        source: *const f32,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_float_iget(
+        dest: *mut f32,
+        source: *const f32,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_double_iput(
+        dest: *mut f64,
+        source: *const f64,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_double_iget(
+        dest: *mut f64,
+        source: *const f64,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_ctx_create(
+        options: ::std::os::raw::c_long,
+        ctx: *mut shmem_ctx_t,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_ctx_destroy(ctx: shmem_ctx_t);
+}
+unsafe extern "C" {
+    pub fn shmem_ctx_putmem(
+        ctx: shmem_ctx_t,
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_ctx_getmem(
+        ctx: shmem_ctx_t,
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_ctx_fence(ctx: shmem_ctx_t);
+}
+unsafe extern "C" {
+    pub fn shmem_ctx_quiet(ctx: shmem_ctx_t);
+}
+unsafe extern "C" {
+    pub fn shmem_team_my_pe(team: shmem_team_t) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_team_n_pes(team: shmem_team_t) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_team_split_strided(
+        parent_team: shmem_team_t,
+        start: ::std::os::raw::c_int,
+        stride: ::std::os::raw::c_int,
+        size: ::std::os::raw::c_int,
+        config: *const shmem_team_config_t,
+        config_mask: ::std::os::raw::c_long,
+        new_team: *mut shmem_team_t,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_team_destroy(team: shmem_team_t);
+}
+unsafe extern "C" {
+    pub fn shmem_int_atomic_fetch(
+        source: *const ::std::os::raw::c_int,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int_atomic_add(
+        dest: *mut ::std::os::raw::c_int,
+        value: ::std::os::raw::c_int,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_int_atomic_compare_swap(
+        dest: *mut ::std::os::raw::c_int,
+        cond: ::std::os::raw::c_int,
+        value: ::std::os::raw::c_int,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int_atomic_swap(
+        dest: *mut ::std::os::raw::c_int,
+        value: ::std::os::raw::c_int,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int_atomic_fetch_add(
+        dest: *mut ::std::os::raw::c_int,
+        value: ::std::os::raw::c_int,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_atomic_fetch(
+        source: *const ::std::os::raw::c_uint,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_uint;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_atomic_add(
+        dest: *mut ::std::os::raw::c_uint,
+        value: ::std::os::raw::c_uint,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_uint_atomic_compare_swap(
+        dest: *mut ::std::os::raw::c_uint,
+        cond: ::std::os::raw::c_uint,
+        value: ::std::os::raw::c_uint,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_uint;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_atomic_swap(
+        dest: *mut ::std::os::raw::c_uint,
+        value: ::std::os::raw::c_uint,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_uint;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_atomic_fetch_add(
+        dest: *mut ::std::os::raw::c_uint,
+        value: ::std::os::raw::c_uint,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_uint;
+}
+unsafe extern "C" {
+    pub fn shmem_long_atomic_fetch(
+        source: *const ::std::os::raw::c_long,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_long;
+}
+unsafe extern "C" {
+    pub fn shmem_long_atomic_fetch_inc(
+        dest: *mut ::std::os::raw::c_long,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_long;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_atomic_fetch(
+        source: *const ::std::os::raw::c_longlong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_longlong;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_atomic_add(
+        dest: *mut ::std::os::raw::c_longlong,
+        value: ::std::os::raw::c_longlong,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_atomic_compare_swap(
+        dest: *mut ::std::os::raw::c_longlong,
+        cond: ::std::os::raw::c_longlong,
+        value: ::std::os::raw::c_longlong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_longlong;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_atomic_swap(
+        dest: *mut ::std::os::raw::c_longlong,
+        value: ::std::os::raw::c_longlong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_longlong;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_atomic_fetch_add(
+        dest: *mut ::std::os::raw::c_longlong,
+        value: ::std::os::raw::c_longlong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_longlong;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_atomic_fetch(
+        source: *const ::std::os::raw::c_ulonglong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_atomic_add(
+        dest: *mut ::std::os::raw::c_ulonglong,
+        value: ::std::os::raw::c_ulonglong,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_atomic_compare_swap(
+        dest: *mut ::std::os::raw::c_ulonglong,
+        cond: ::std::os::raw::c_ulonglong,
+        value: ::std::os::raw::c_ulonglong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_atomic_swap(
+        dest: *mut ::std::os::raw::c_ulonglong,
+        value: ::std::os::raw::c_ulonglong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_atomic_fetch_add(
+        dest: *mut ::std::os::raw::c_ulonglong,
+        value: ::std::os::raw::c_ulonglong,
+        pe: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shmem_int_wait_until_all(
+        ivars: *mut ::std::os::raw::c_int,
+        nelems: usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_int_wait_until_any(
+        ivars: *mut ::std::os::raw::c_int,
+        nelems: usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_int,
+    ) -> usize;
+}
+unsafe extern "C" {
+    pub fn shmem_int_wait_until_some(
+        ivars: *mut ::std::os::raw::c_int,
+        nelems: usize,
+        indices: *mut usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_int,
+    ) -> usize;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_wait_until_all(
+        ivars: *mut ::std::os::raw::c_uint,
+        nelems: usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_uint,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_uint_wait_until_any(
+        ivars: *mut ::std::os::raw::c_uint,
+        nelems: usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_uint,
+    ) -> usize;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_wait_until_some(
+        ivars: *mut ::std::os::raw::c_uint,
+        nelems: usize,
+        indices: *mut usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_uint,
+    ) -> usize;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_wait_until_all(
+        ivars: *mut ::std::os::raw::c_longlong,
+        nelems: usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_longlong,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_wait_until_any(
+        ivars: *mut ::std::os::raw::c_longlong,
+        nelems: usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_longlong,
+    ) -> usize;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_wait_until_some(
+        ivars: *mut ::std::os::raw::c_longlong,
+        nelems: usize,
+        indices: *mut usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_longlong,
+    ) -> usize;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_wait_until_all(
+        ivars: *mut ::std::os::raw::c_ulonglong,
+        nelems: usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_ulonglong,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_wait_until_any(
+        ivars: *mut ::std::os::raw::c_ulonglong,
+        nelems: usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_ulonglong,
+    ) -> usize;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_wait_until_some(
+        ivars: *mut ::std::os::raw::c_ulonglong,
+        nelems: usize,
+        indices: *mut usize,
+        status: *const ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_ulonglong,
+    ) -> usize;
+}
+unsafe extern "C" {
+    pub fn shmem_int_wait_until(
+        ivar: *mut ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_int_test(
+        ivar: *mut ::std::os::raw::c_int,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_wait_until(
+        ivar: *mut ::std::os::raw::c_uint,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_uint,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_uint_test(
+        ivar: *mut ::std::os::raw::c_uint,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_wait_until(
+        ivar: *mut ::std::os::raw::c_longlong,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_longlong,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_test(
+        ivar: *mut ::std::os::raw::c_longlong,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_longlong,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_wait_until(
+        ivar: *mut ::std::os::raw::c_ulonglong,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_ulonglong,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_test(
+        ivar: *mut ::std::os::raw::c_ulonglong,
+        cmp: ::std::os::raw::c_int,
+        cmp_value: ::std::os::raw::c_ulonglong,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_putmem_signal(
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+        sig_addr: *mut u64,
+        signal: u64,
+        sig_op: ::std::os::raw::c_int,
+        pe: ::std::os::raw::c_int,
+    );
+}
+unsafe extern "C" {
+    pub fn shmem_signal_fetch(sig_addr: *const u64) -> u64;
+}
+unsafe extern "C" {
+    pub fn shmem_broadcastmem(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+        PE_root: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_collectmem(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_fcollectmem(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_void,
+        source: *const ::std::os::raw::c_void,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int_alltoall(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_int,
+        source: *const ::std::os::raw::c_int,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int_alltoalls(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_int,
+        source: *const ::std::os::raw::c_int,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_alltoall(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_uint,
+        source: *const ::std::os::raw::c_uint,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint_alltoalls(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_uint,
+        source: *const ::std::os::raw::c_uint,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_alltoall(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_longlong,
+        source: *const ::std::os::raw::c_longlong,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_longlong_alltoalls(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_longlong,
+        source: *const ::std::os::raw::c_longlong,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_alltoall(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_ulonglong,
+        source: *const ::std::os::raw::c_ulonglong,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_ulonglong_alltoalls(
+        team: shmem_team_t,
+        dest: *mut ::std::os::raw::c_ulonglong,
+        source: *const ::std::os::raw::c_ulonglong,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_float_alltoall(
+        team: shmem_team_t,
+        dest: *mut f32,
+        source: *const f32,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_float_alltoalls(
+        team: shmem_team_t,
+        dest: *mut f32,
+        source: *const f32,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_double_alltoall(
+        team: shmem_team_t,
+        dest: *mut f64,
+        source: *const f64,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_double_alltoalls(
+        team: shmem_team_t,
+        dest: *mut f64,
+        source: *const f64,
+        dst: ptrdiff_t,
+        sst: ptrdiff_t,
+        nelems: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int32_and_reduce(
+        team: shmem_team_t,
+        dest: *mut i32,
+        source: *const i32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int32_or_reduce(
+        team: shmem_team_t,
+        dest: *mut i32,
+        source: *const i32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int32_xor_reduce(
+        team: shmem_team_t,
+        dest: *mut i32,
+        source: *const i32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int32_max_reduce(
+        team: shmem_team_t,
+        dest: *mut i32,
+        source: *const i32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int32_min_reduce(
+        team: shmem_team_t,
+        dest: *mut i32,
+        source: *const i32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int32_sum_reduce(
+        team: shmem_team_t,
+        dest: *mut i32,
+        source: *const i32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int32_prod_reduce(
+        team: shmem_team_t,
+        dest: *mut i32,
+        source: *const i32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int64_and_reduce(
+        team: shmem_team_t,
+        dest: *mut i64,
+        source: *const i64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int64_or_reduce(
+        team: shmem_team_t,
+        dest: *mut i64,
+        source: *const i64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int64_xor_reduce(
+        team: shmem_team_t,
+        dest: *mut i64,
+        source: *const i64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int64_max_reduce(
+        team: shmem_team_t,
+        dest: *mut i64,
+        source: *const i64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int64_min_reduce(
+        team: shmem_team_t,
+        dest: *mut i64,
+        source: *const i64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int64_sum_reduce(
+        team: shmem_team_t,
+        dest: *mut i64,
+        source: *const i64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_int64_prod_reduce(
+        team: shmem_team_t,
+        dest: *mut i64,
+        source: *const i64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint32_and_reduce(
+        team: shmem_team_t,
+        dest: *mut u32,
+        source: *const u32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint32_or_reduce(
+        team: shmem_team_t,
+        dest: *mut u32,
+        source: *const u32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint32_xor_reduce(
+        team: shmem_team_t,
+        dest: *mut u32,
+        source: *const u32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint32_max_reduce(
+        team: shmem_team_t,
+        dest: *mut u32,
+        source: *const u32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint32_min_reduce(
+        team: shmem_team_t,
+        dest: *mut u32,
+        source: *const u32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint32_sum_reduce(
+        team: shmem_team_t,
+        dest: *mut u32,
+        source: *const u32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint32_prod_reduce(
+        team: shmem_team_t,
+        dest: *mut u32,
+        source: *const u32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint64_and_reduce(
+        team: shmem_team_t,
+        dest: *mut u64,
+        source: *const u64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint64_or_reduce(
+        team: shmem_team_t,
+        dest: *mut u64,
+        source: *const u64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint64_xor_reduce(
+        team: shmem_team_t,
+        dest: *mut u64,
+        source: *const u64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint64_max_reduce(
+        team: shmem_team_t,
+        dest: *mut u64,
+        source: *const u64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint64_min_reduce(
+        team: shmem_team_t,
+        dest: *mut u64,
+        source: *const u64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint64_sum_reduce(
+        team: shmem_team_t,
+        dest: *mut u64,
+        source: *const u64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_uint64_prod_reduce(
+        team: shmem_team_t,
+        dest: *mut u64,
+        source: *const u64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_float_max_reduce(
+        team: shmem_team_t,
+        dest: *mut f32,
+        source: *const f32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_float_min_reduce(
+        team: shmem_team_t,
+        dest: *mut f32,
+        source: *const f32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_float_sum_reduce(
+        team: shmem_team_t,
+        dest: *mut f32,
+        source: *const f32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_float_prod_reduce(
+        team: shmem_team_t,
+        dest: *mut f32,
+        source: *const f32,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_double_max_reduce(
+        team: shmem_team_t,
+        dest: *mut f64,
+        source: *const f64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_double_min_reduce(
+        team: shmem_team_t,
+        dest: *mut f64,
+        source: *const f64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_double_sum_reduce(
+        team: shmem_team_t,
+        dest: *mut f64,
+        source: *const f64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn shmem_double_prod_reduce(
+        team: shmem_team_t,
+        dest: *mut f64,
+        source: *const f64,
+        nreduce: usize,
+    ) -> ::std::os::raw::c_int;
+}
@@ -0,0 +1,285 @@
+//! Team based collective data movement.
+//!
+//! All buffers passed here must be symmetric memory, and every PE of the team has to make the
+//! same call.
+
+use std::ffi::c_void;
+use std::mem;
+use std::os::raw::{c_double, c_float, c_int, c_longlong, c_uint, c_ulonglong};
+
+use crate::rma::strided::checked_stride;
+use crate::team::Team;
+use crate::{ShmemError, ptrdiff_t, shmem_team_t};
+
+/// turns the status returned by a collective routine into a `Result`
+fn check(status: i32) -> Result<(), ShmemError> {
+    if status == 0 {
+        Ok(())
+    } else {
+        Err(ShmemError::CollectiveFailed(status))
+    }
+}
+
+/// fails with [`ShmemError::InvalidRoot`] unless `root` numbers one of `n_pes` team members
+fn check_root(root: i32, n_pes: i32) -> Result<(), ShmemError> {
+    if (0..n_pes).contains(&root) {
+        Ok(())
+    } else {
+        Err(ShmemError::InvalidRoot { root, n_pes })
+    }
+}
+
+/// panics unless `dest_len` elements fit what `n_pes` members contributing `src_len` each send
+///
+/// A total beyond `usize::MAX` can't fit any `dest` and fails the same way.
+fn check_fcollect_len(dest_len: usize, n_pes: i32, src_len: usize) {
+    let needed = src_len.checked_mul(n_pes as usize);
+    assert!(
+        needed.is_some_and(|needed| dest_len >= needed),
+        "fcollect: dest holds {} elements but {} PEs contribute {} each ({} total)",
+        dest_len,
+        n_pes,
+        src_len,
+        needed.map_or_else(|| String::from("more than usize::MAX"), |n| n.to_string())
+    );
+}
+
+/// copies `data` from the PE numbered `root` within `team` to the `data` of all other members
+///
+/// A `root` outside the team fails with [`ShmemError::InvalidRoot`] before anything is sent,
+/// the library would wait for it forever.
+pub fn broadcast<T: Copy>(team: &Team, root: i32, data: &mut [T]) -> Result<(), ShmemError> {
+    check_root(root, team.n_pes())?;
+    let status = unsafe {
+        crate::shmem_broadcastmem(
+            team.as_raw(),
+            data.as_mut_ptr() as *mut c_void,
+            data.as_ptr() as *const c_void,
+            mem::size_of_val(data),
+            root,
+        )
+    };
+    check(status)
+}
+
+/// concatenates the `src` of all members, in team order, into `dest` on every member
+///
+/// PEs may contribute different lengths, so `dest` can only be checked against the local `src`;
+/// it has to hold the sum of all contributions.
+///
+/// # Panics
+///
+/// If `dest` is shorter than `src`.
+pub fn collect<T: Copy>(team: &Team, dest: &mut [T], src: &[T]) -> Result<(), ShmemError> {
+    assert!(
+        dest.len() >= src.len(),
+        "collect: dest holds {} elements but this PE alone contributes {}",
+        dest.len(),
+        src.len()
+    );
+    let status = unsafe {
+        crate::shmem_collectmem(
+            team.as_raw(),
+            dest.as_mut_ptr() as *mut c_void,
+            src.as_ptr() as *const c_void,
+            mem::size_of_val(src),
+        )
+    };
+    check(status)
+}
+
+/// like [`collect`], for when every member contributes the same number of elements
+///
+/// # Panics
+///
+/// If `dest` is shorter than `src.len() * team.n_pes()`.
+pub fn fcollect<T: Copy>(team: &Team, dest: &mut [T], src: &[T]) -> Result<(), ShmemError> {
+    check_fcollect_len(dest.len(), team.n_pes(), src.len());
+    let status = unsafe {
+        crate::shmem_fcollectmem(
+            team.as_raw(),
+            dest.as_mut_ptr() as *mut c_void,
+            src.as_ptr() as *const c_void,
+            mem::size_of_val(src),
+        )
+    };
+    check(status)
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Element types with typed all-to-all routines
+///
+/// This is sealed, the library only provides the routines for a fixed set of types.
+pub trait ShmemAlltoall: Copy + private::Sealed {
+    #[doc(hidden)]
+    unsafe fn alltoall(team: shmem_team_t, dest: *mut Self, src: *const Self, nelems: usize)
+    -> i32;
+    #[doc(hidden)]
+    unsafe fn alltoalls(
+        team: shmem_team_t,
+        dest: *mut Self,
+        src: *const Self,
+        dst_stride: ptrdiff_t,
+        src_stride: ptrdiff_t,
+        nelems: usize,
+    ) -> i32;
+}
+
+macro_rules! impl_alltoall {
+    ($t:ty, $c:ty, $alltoall:ident, $alltoalls:ident) => {
+        impl private::Sealed for $t {}
+
+        impl ShmemAlltoall for $t {
+            unsafe fn alltoall(
+                team: shmem_team_t,
+                dest: *mut Self,
+                src: *const Self,
+                nelems: usize,
+            ) -> i32 {
+                unsafe { crate::$alltoall(team, dest as *mut $c, src as *const $c, nelems) }
+            }
+
+            unsafe fn alltoalls(
+                team: shmem_team_t,
+                dest: *mut Self,
+                src: *const Self,
+                dst_stride: ptrdiff_t,
+                src_stride: ptrdiff_t,
+                nelems: usize,
+            ) -> i32 {
+                unsafe {
+                    crate::$alltoalls(
+                        team,
+                        dest as *mut $c,
+                        src as *const $c,
+                        dst_stride,
+                        src_stride,
+                        nelems,
+                    )
+                }
+            }
+        }
+    };
+}
+
+impl_alltoall!(i32, c_int, shmem_int_alltoall, shmem_int_alltoalls);
+impl_alltoall!(u32, c_uint, shmem_uint_alltoall, shmem_uint_alltoalls);
+impl_alltoall!(
+    i64,
+    c_longlong,
+    shmem_longlong_alltoall,
+    shmem_longlong_alltoalls
+);
+impl_alltoall!(
+    u64,
+    c_ulonglong,
+    shmem_ulonglong_alltoall,
+    shmem_ulonglong_alltoalls
+);
+impl_alltoall!(f32, c_float, shmem_float_alltoall, shmem_float_alltoalls);
+impl_alltoall!(f64, c_double, shmem_double_alltoall, shmem_double_alltoalls);
+
+/// sends block `j` of `src` to every member `j` of `team`, which stores it as block `i` of its
+/// `dest`, where `i` is the number of the calling PE
+///
+/// Both slices are split into `team.n_pes()` equally sized blocks.
+///
+/// # Panics
+///
+/// If `dest` and `src` differ in length or the length is not a multiple of `team.n_pes()`.
+pub fn alltoall<T: ShmemAlltoall>(
+    team: &Team,
+    dest: &mut [T],
+    src: &[T],
+) -> Result<(), ShmemError> {
+    let n_pes = team.n_pes() as usize;
+    assert_eq!(dest.len(), src.len(), "alltoall: length mismatch");
+    assert!(
+        n_pes > 0 && src.len().is_multiple_of(n_pes),
+        "alltoall: {} elements don't split into {} blocks",
+        src.len(),
+        n_pes
+    );
+    let status = unsafe {
+        T::alltoall(
+            team.as_raw(),
+            dest.as_mut_ptr(),
+            src.as_ptr(),
+            src.len() / n_pes,
+        )
+    };
+    check(status)
+}
+
+/// like [`alltoall`] with blocks of `nelems` elements, reading every `src_stride`th element of
+/// `src` and writing every `dst_stride`th element of `dest`
+///
+/// The blocks follow each other at the same stride, so both slices must hold
+/// `(team.n_pes() * nelems - 1) * stride + 1` elements.
+///
+/// # Panics
+///
+/// If a stride is 0 or a slice is too short for `team.n_pes()` blocks at its stride.
+pub fn alltoalls<T: ShmemAlltoall>(
+    team: &Team,
+    dest: &mut [T],
+    src: &[T],
+    dst_stride: usize,
+    src_stride: usize,
+    nelems: usize,
+) -> Result<(), ShmemError> {
+    let total = team.n_pes() as usize * nelems;
+    let dst_stride = checked_stride("alltoalls dest", dest.len(), dst_stride, total);
+    let src_stride = checked_stride("alltoalls src", src.len(), src_stride, total);
+    let status = unsafe {
+        T::alltoalls(
+            team.as_raw(),
+            dest.as_mut_ptr(),
+            src.as_ptr(),
+            dst_stride,
+            src_stride,
+            nelems,
+        )
+    };
+    check(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_must_be_a_team_member() {
+        assert_eq!(check_root(0, 4), Ok(()));
+        assert_eq!(check_root(3, 4), Ok(()));
+        assert_eq!(
+            check_root(4, 4),
+            Err(ShmemError::InvalidRoot { root: 4, n_pes: 4 })
+        );
+        assert_eq!(
+            check_root(-1, 4),
+            Err(ShmemError::InvalidRoot { root: -1, n_pes: 4 })
+        );
+    }
+
+    #[test]
+    fn fcollect_dest_fits_all_contributions() {
+        check_fcollect_len(12, 4, 3);
+        check_fcollect_len(13, 4, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "fcollect: dest holds 11 elements but 4 PEs contribute 3 each")]
+    fn fcollect_short_dest_panics() {
+        check_fcollect_len(11, 4, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "fcollect: dest holds 8 elements but 4 PEs contribute")]
+    fn fcollect_overflowing_total_panics() {
+        check_fcollect_len(8, 4, usize::MAX / 2);
+    }
+}
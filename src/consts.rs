@@ -0,0 +1,73 @@
+//! The commonly used SHMEM constants, with the integer type the API takes them as.
+//!
+//! Depending on the implementation the bindings define these as plain macros or as members of
+//! anonymous enums, so their generated types differ. The values here are the same constants cast
+//! to the parameter type of the routines that accept them. Constants newer than the installed
+//! spec version are left out.
+
+#![allow(clippy::unnecessary_cast)]
+
+#[cfg(feature = "newtype-enums")]
+use std::mem;
+use std::os::raw::{c_int, c_long};
+
+/// defines `c_int` constants for constants that may be generated as newtype enums
+macro_rules! enum_consts {
+    ($($(#[$attr:meta])* $name:ident),* $(,)?) => {$(
+        $(#[$attr])*
+        #[cfg(not(feature = "newtype-enums"))]
+        pub const $name: c_int = crate::$name as c_int;
+        // The newtypes are `#[repr(transparent)]` around an `int` sized integer, and so are the
+        // constants that stay plain.
+        $(#[$attr])*
+        #[cfg(feature = "newtype-enums")]
+        #[allow(clippy::useless_transmute)]
+        pub const $name: c_int = unsafe { mem::transmute(crate::$name) };
+    )*};
+}
+
+/// Thread levels for `shmem_init_thread`
+#[cfg(shmem_1_4)]
+pub const SHMEM_THREAD_SINGLE: c_int = crate::SHMEM_THREAD_SINGLE as c_int;
+#[cfg(shmem_1_4)]
+pub const SHMEM_THREAD_FUNNELED: c_int = crate::SHMEM_THREAD_FUNNELED as c_int;
+#[cfg(shmem_1_4)]
+pub const SHMEM_THREAD_SERIALIZED: c_int = crate::SHMEM_THREAD_SERIALIZED as c_int;
+#[cfg(shmem_1_4)]
+pub const SHMEM_THREAD_MULTIPLE: c_int = crate::SHMEM_THREAD_MULTIPLE as c_int;
+
+enum_consts! {
+    /// Comparisons for the `wait_until` and `test` routines
+    SHMEM_CMP_EQ,
+    SHMEM_CMP_NE,
+    SHMEM_CMP_GT,
+    SHMEM_CMP_GE,
+    SHMEM_CMP_LT,
+    SHMEM_CMP_LE,
+}
+
+enum_consts! {
+    /// Signal update operations for the put-with-signal routines
+    #[cfg(shmem_1_5)]
+    SHMEM_SIGNAL_SET,
+    #[cfg(shmem_1_5)]
+    SHMEM_SIGNAL_ADD,
+}
+
+/// Option bits for `shmem_ctx_create`
+#[cfg(shmem_1_4)]
+pub const SHMEM_CTX_SERIALIZED: c_long = crate::SHMEM_CTX_SERIALIZED as c_long;
+#[cfg(shmem_1_4)]
+pub const SHMEM_CTX_PRIVATE: c_long = crate::SHMEM_CTX_PRIVATE as c_long;
+#[cfg(shmem_1_4)]
+pub const SHMEM_CTX_NOSTORE: c_long = crate::SHMEM_CTX_NOSTORE as c_long;
+
+/// Usage hints for `shmem_malloc_with_hints`
+#[cfg(shmem_1_5)]
+pub const SHMEM_MALLOC_ATOMICS_REMOTE: c_long = crate::SHMEM_MALLOC_ATOMICS_REMOTE as c_long;
+#[cfg(shmem_1_5)]
+pub const SHMEM_MALLOC_SIGNAL_REMOTE: c_long = crate::SHMEM_MALLOC_SIGNAL_REMOTE as c_long;
+
+/// Configuration mask bits for `shmem_team_split_strided`
+#[cfg(shmem_1_5)]
+pub const SHMEM_TEAM_NUM_CONTEXTS: c_long = crate::SHMEM_TEAM_NUM_CONTEXTS as c_long;
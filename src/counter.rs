@@ -0,0 +1,61 @@
+//! A counter shared by all PEs over `shmem_long_atomic_fetch_inc` and
+//! `shmem_long_atomic_fetch`.
+
+use std::cell::UnsafeCell;
+use std::os::raw::c_long;
+
+use crate::ShmemError;
+use crate::mem::SymmetricBox;
+use crate::sync::barrier_all;
+
+/// A symmetric `long` on one PE that every PE increments atomically, e.g. to hand out work items
+///
+/// Every PE allocates the symmetric word, but only the copy on the owning PE is used; the
+/// counter keeps track of which PE that is. Creating and dropping a counter is collective like
+/// any symmetric allocation, and all PEs must name the same owner.
+pub struct DistributedCounter {
+    word: SymmetricBox<UnsafeCell<c_long>>,
+    owner: i32,
+}
+
+impl DistributedCounter {
+    /// allocates a counter at 0 kept on PE `owner`
+    ///
+    /// This ends with a barrier, so no PE can increment the counter before the owner cleared it.
+    ///
+    /// # Panics
+    ///
+    /// If `owner` is not a PE number.
+    pub fn new(owner: i32) -> Result<DistributedCounter, ShmemError> {
+        let n_pes = unsafe { crate::shmem_n_pes() };
+        assert!(
+            (0..n_pes).contains(&owner),
+            "DistributedCounter: owner {} is not a PE, there are {}",
+            owner,
+            n_pes
+        );
+        let word = SymmetricBox::new(UnsafeCell::new(0))?;
+        barrier_all();
+        Ok(DistributedCounter { word, owner })
+    }
+
+    /// the PE the counter is kept on
+    pub fn owner(&self) -> i32 {
+        self.owner
+    }
+
+    /// increments the counter, returning the value before
+    ///
+    /// Each value is returned to exactly one caller across all PEs.
+    pub fn fetch_inc(&self) -> i64 {
+        // `long` is only 32 bits on some targets.
+        unsafe { crate::shmem_long_atomic_fetch_inc(self.word.get(), self.owner) as i64 }
+    }
+
+    /// the current value of the counter
+    ///
+    /// Other PEs may increment it right after, so this is only exact once they stopped.
+    pub fn read(&self) -> i64 {
+        unsafe { crate::shmem_long_atomic_fetch(self.word.get(), self.owner) as i64 }
+    }
+}
@@ -0,0 +1,105 @@
+//! Communication contexts (`shmem_ctx_t`).
+
+use std::ffi::c_void;
+use std::mem;
+use std::os::raw::c_long;
+
+use crate::{ShmemError, shmem_ctx_t};
+
+#[derive(Debug)]
+enum Handle {
+    /// `SHMEM_CTX_DEFAULT`, which is owned by the library and never destroyed
+    Default,
+    /// created with `shmem_ctx_create`, destroyed on drop
+    Owned(shmem_ctx_t),
+}
+
+/// A communication context, operations on it are ordered and completed independently of other
+/// contexts
+#[derive(Debug)]
+pub struct Context {
+    handle: Handle,
+}
+
+impl Context {
+    /// creates a context with the given [`consts`](crate::consts) `SHMEM_CTX_*` option bits
+    pub fn create(options: c_long) -> Result<Context, ShmemError> {
+        let mut ctx = unsafe { mem::zeroed() };
+        let status = unsafe { crate::shmem_ctx_create(options, &mut ctx) };
+        if status != 0 {
+            return Err(ShmemError::ContextCreateFailed(status));
+        }
+        Ok(Context {
+            handle: Handle::Owned(ctx),
+        })
+    }
+
+    /// the default context used by all operations that don't take one
+    pub fn default_context() -> Context {
+        Context {
+            handle: Handle::Default,
+        }
+    }
+
+    /// the raw handle, for use with the `shmem_ctx_*` bindings
+    pub fn as_raw(&self) -> shmem_ctx_t {
+        match self.handle {
+            Handle::Default => unsafe { crate::SHMEM_CTX_DEFAULT },
+            Handle::Owned(ctx) => ctx,
+        }
+    }
+
+    /// copies `src` into the symmetric `dest` on PE `pe`, like [`crate::rma::put`]
+    ///
+    /// # Panics
+    ///
+    /// If `dest` and `src` differ in length.
+    pub fn put<T: Copy>(&self, dest: &mut [T], src: &[T], pe: i32) {
+        assert_eq!(dest.len(), src.len(), "put: length mismatch");
+        unsafe {
+            crate::shmem_ctx_putmem(
+                self.as_raw(),
+                dest.as_mut_ptr() as *mut c_void,
+                src.as_ptr() as *const c_void,
+                mem::size_of_val(src),
+                pe,
+            )
+        };
+    }
+
+    /// copies the symmetric `src` on PE `pe` into `dest`, like [`crate::rma::get`]
+    ///
+    /// # Panics
+    ///
+    /// If `dest` and `src` differ in length.
+    pub fn get<T: Copy>(&self, dest: &mut [T], src: &[T], pe: i32) {
+        assert_eq!(dest.len(), src.len(), "get: length mismatch");
+        unsafe {
+            crate::shmem_ctx_getmem(
+                self.as_raw(),
+                dest.as_mut_ptr() as *mut c_void,
+                src.as_ptr() as *const c_void,
+                mem::size_of_val(src),
+                pe,
+            )
+        };
+    }
+
+    /// orders the operations issued on this context so far to each PE before any issued later
+    pub fn fence(&self) {
+        unsafe { crate::shmem_ctx_fence(self.as_raw()) };
+    }
+
+    /// waits until all operations issued on this context have completed
+    pub fn quiet(&self) {
+        unsafe { crate::shmem_ctx_quiet(self.as_raw()) };
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        if let Handle::Owned(ctx) = self.handle {
+            unsafe { crate::shmem_ctx_destroy(ctx) };
+        }
+    }
+}
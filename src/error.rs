@@ -0,0 +1,100 @@
+use std::error::Error;
+use std::fmt;
+
+#[cfg(shmem_1_4)]
+use crate::ThreadLevel;
+#[cfg(shmem_1_5)]
+use crate::reduce::ReduceOp;
+
+/// Errors reported by the safe wrappers
+///
+/// Everything in this crate that can fail in the C library (a status code or a sentinel handle)
+/// reports it as one of these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShmemError {
+    /// the OpenSHMEM library was already initialized by this process
+    AlreadyInitialized,
+    /// the library failed to initialize, with the returned status code
+    InitFailed(i32),
+    /// the library can't provide the thread level the program needs
+    #[cfg(shmem_1_4)]
+    ThreadLevelUnsupported {
+        required: ThreadLevel,
+        provided: ThreadLevel,
+    },
+    /// `shmem_init_thread` provided a thread level that is none of the `SHMEM_THREAD_*` ones
+    #[cfg(shmem_1_4)]
+    UnknownThreadLevel(i32),
+    /// the symmetric heap could not satisfy an allocation of `size` bytes
+    AllocFailed { size: usize },
+    /// `shmem_ctx_create` failed with the returned status code
+    ContextCreateFailed(i32),
+    /// `shmem_team_split_strided` failed with the returned status code
+    TeamSplitFailed(i32),
+    /// the calling PE is not a member of the team that was created
+    InvalidTeam,
+    /// a collective routine failed with the returned status code
+    CollectiveFailed(i32),
+    /// the root of a collective is not a PE number within the team of `n_pes` PEs
+    InvalidRoot { root: i32, n_pes: i32 },
+    /// the library has no reduction routine for `op` on the type `ty`
+    #[cfg(shmem_1_5)]
+    UnsupportedReduction { op: ReduceOp, ty: &'static str },
+}
+
+impl fmt::Display for ShmemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShmemError::AlreadyInitialized => write!(f, "OpenSHMEM is already initialized"),
+            ShmemError::InitFailed(status) => {
+                write!(f, "OpenSHMEM failed to initialize (status {})", status)
+            }
+            #[cfg(shmem_1_4)]
+            ShmemError::ThreadLevelUnsupported { required, provided } => write!(
+                f,
+                "thread level {:?} is required but only {:?} is provided",
+                required, provided
+            ),
+            #[cfg(shmem_1_4)]
+            ShmemError::UnknownThreadLevel(level) => {
+                write!(f, "OpenSHMEM provided the unknown thread level {}", level)
+            }
+            ShmemError::AllocFailed { size } => {
+                write!(f, "failed to allocate {} bytes of symmetric memory", size)?;
+                match crate::mem::symmetric_heap_size() {
+                    Some(heap) => write!(
+                        f,
+                        " (SHMEM_SYMMETRIC_SIZE limits the symmetric heap to {} bytes)",
+                        heap
+                    ),
+                    None => write!(
+                        f,
+                        " (the symmetric heap may be too small, set SHMEM_SYMMETRIC_SIZE to grow it)"
+                    ),
+                }
+            }
+            ShmemError::ContextCreateFailed(status) => {
+                write!(f, "failed to create a context (status {})", status)
+            }
+            ShmemError::TeamSplitFailed(status) => {
+                write!(f, "failed to split the team (status {})", status)
+            }
+            ShmemError::InvalidTeam => write!(f, "the calling PE is not part of the team"),
+            ShmemError::CollectiveFailed(status) => {
+                write!(f, "collective operation failed (status {})", status)
+            }
+            ShmemError::InvalidRoot { root, n_pes } => write!(
+                f,
+                "root {} is not a member of the team, PEs are numbered 0 to {}",
+                root,
+                n_pes - 1
+            ),
+            #[cfg(shmem_1_5)]
+            ShmemError::UnsupportedReduction { op, ty } => {
+                write!(f, "{:?} reductions are not supported on {}", op, ty)
+            }
+        }
+    }
+}
+
+impl Error for ShmemError {}
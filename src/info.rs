@@ -0,0 +1,37 @@
+//! The implementation's name and spec version, over `shmem_info_get_name` and
+//! `shmem_info_get_version`.
+//!
+//! Both may be called before the library is initialized.
+
+use std::mem::MaybeUninit;
+use std::os::raw::{c_char, c_int};
+
+/// the capacity `shmem_info_get_name` may fill, including the terminating NUL
+const NAME_LEN: usize = crate::limits::SHMEM_MAX_NAME_LEN;
+
+/// the name of the OpenSHMEM implementation, e.g. `Sandia OpenSHMEM`
+///
+/// Bytes that aren't UTF-8 are replaced, the spec only promises a C string.
+pub fn name() -> String {
+    let mut buf = [MaybeUninit::<c_char>::uninit(); NAME_LEN];
+    unsafe { crate::shmem_info_get_name(buf.as_mut_ptr() as *mut c_char) };
+
+    // Only the bytes up to the NUL were written, the rest must not be read.
+    let mut bytes = Vec::new();
+    for byte in &buf {
+        let byte = unsafe { byte.assume_init() } as u8;
+        if byte == 0 {
+            break;
+        }
+        bytes.push(byte);
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// the `(major, minor)` spec version the library implements
+pub fn version() -> (i32, i32) {
+    let mut major: c_int = 0;
+    let mut minor: c_int = 0;
+    unsafe { crate::shmem_info_get_version(&mut major, &mut minor) };
+    (major, minor)
+}
@@ -1,3 +1,36 @@
+//! Raw bindings to the OpenSHMEM C API.
+//!
+//! The build script exports the probed installation to the build scripts of dependent crates
+//! as `DEP_OPENSHMEM_INCLUDE`, `DEP_OPENSHMEM_LIB_PATHS` (both joined like `PATH`),
+//! `DEP_OPENSHMEM_LIBS` (colon separated), `DEP_OPENSHMEM_VERSION`,
+//! `DEP_OPENSHMEM_IMPLEMENTATION` and the path of the bindings this crate was built with as
+//! `DEP_OPENSHMEM_BINDINGS`. Build scripts that need the full probe result can run the
+//! same probe through the `openshmem-build` crate instead.
+//!
+//! To see what the build script would find without building, run that probe from the workspace
+//! root with `cargo run -p openshmem-build --bin openshmem-probe`.
+//!
+//! Sites can skip probing by pointing `OPENSHMEM_SYS_CONFIG` at a file listing the `libs`,
+//! `lib_paths` and `include_paths` to use, see `openshmem_build::Library::from_site_config`.
+//!
+//! Sizes and offsets are plain Rust integers in the generated bindings: `size_t` and
+//! `shmem_size_t` are `usize`, `ptrdiff_t` and `shmem_ptrdiff_t` are `isize`, whichever of them
+//! the headers use. Struct tags with leading underscores, like `_shmem_team_config`, lose them.
+//!
+//! Safe wrappers around routines newer than OpenSHMEM 1.3 are only compiled when the detected
+//! library implements the spec version that introduced them, through the `shmem_1_4` and
+//! `shmem_1_5` cfgs the build script sets. An undetected version enables everything.
+//!
+//! With the `complex` feature the bindings include the complex reductions. Their `float
+//! _Complex` and `double _Complex` arguments come out as bindgen's `__BindgenComplex<f32>` and
+//! `__BindgenComplex<f64>`, `#[repr(C)]` structs of the real and imaginary part. That matches
+//! every C ABI the crate is built for, where a complex number is laid out like `[f64; 2]`.
+//!
+//! The build script also sets `shmem_transport_ucx`, `shmem_transport_libfabric` and
+//! `shmem_transport_verbs` when the wrapper links the matching libraries, and exports the same
+//! names as `DEP_OPENSHMEM_TRANSPORTS` (colon separated). These reflect the link line only, not
+//! the transport the runtime ends up selecting, which may be loaded as a plugin instead.
+
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
@@ -6,4 +39,37 @@
 #![allow(improper_ctypes)]
 
 use std::{ffi::c_void, mem::MaybeUninit};
+#[cfg(not(feature = "vendored-bindings"))]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+#[cfg(feature = "vendored-bindings")]
+include!("bindings_prebuilt.rs");
+
+pub mod accessibility;
+#[cfg(shmem_1_4)]
+pub mod atomic;
+#[cfg(shmem_1_5)]
+pub mod collectives;
+pub mod consts;
+#[cfg(shmem_1_4)]
+pub mod counter;
+#[cfg(shmem_1_4)]
+pub mod ctx;
+mod error;
+pub mod info;
+pub mod limits;
+pub mod lock;
+pub mod mem;
+#[cfg(shmem_1_5)]
+pub mod reduce;
+pub mod rma;
+mod runtime;
+pub mod signal;
+pub mod sync;
+#[cfg(shmem_1_5)]
+pub mod team;
+pub mod timing;
+
+pub use error::ShmemError;
+pub use runtime::Shmem;
+#[cfg(shmem_1_4)]
+pub use runtime::ThreadLevel;
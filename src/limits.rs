@@ -0,0 +1,10 @@
+//! Size constants the build script guarantees, whether or not bindgen kept their macros.
+//!
+//! The headers often define these in ways bindgen drops, e.g. with a cast. The build script
+//! reads them from a small compiled program instead, falling back to the bindings, so exactly
+//! these are always here: `SHMEM_MAX_NAME_LEN`, `SHMEM_MAJOR_VERSION` and
+//! `SHMEM_MINOR_VERSION`.
+
+use std::os::raw::c_int;
+
+include!(concat!(env!("OUT_DIR"), "/limits.rs"));
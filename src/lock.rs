@@ -0,0 +1,78 @@
+//! Distributed mutual exclusion over `shmem_set_lock`, `shmem_clear_lock` and
+//! `shmem_test_lock`.
+
+use std::cell::UnsafeCell;
+use std::os::raw::c_long;
+
+use crate::ShmemError;
+use crate::mem::SymmetricBox;
+use crate::sync::barrier_all;
+
+/// A lock shared by all PEs, held by at most one of them at a time
+///
+/// The lock word is a symmetric `long` this type allocates itself, so it always has the size
+/// and address the library expects. Creating a lock is collective like any symmetric
+/// allocation: every PE creates its locks in the same order, and the PEs agree on which lock
+/// protects what by that order. Dropping it is collective too, and no PE may still hold it.
+///
+/// The lock only excludes other PEs. Threads of one PE must not contend for it.
+pub struct DistributedLock {
+    word: SymmetricBox<UnsafeCell<c_long>>,
+}
+
+impl DistributedLock {
+    /// allocates a new, unlocked lock
+    ///
+    /// This ends with a barrier, so no PE can take the lock before all of them cleared their
+    /// copy of the word.
+    pub fn new() -> Result<DistributedLock, ShmemError> {
+        let word = SymmetricBox::new(UnsafeCell::new(0))?;
+        barrier_all();
+        Ok(DistributedLock { word })
+    }
+
+    fn raw(&self) -> *mut c_long {
+        self.word.get()
+    }
+
+    /// blocks until this PE holds the lock
+    ///
+    /// Taking a lock this PE already holds deadlocks.
+    pub fn lock(&self) {
+        unsafe { crate::shmem_set_lock(self.raw()) };
+    }
+
+    /// releases the lock, which this PE must hold
+    ///
+    /// Puts and atomics issued while holding the lock are completed before it is released.
+    pub fn unlock(&self) {
+        unsafe { crate::shmem_clear_lock(self.raw()) };
+    }
+
+    /// takes the lock if no PE holds it, returning whether this PE now does
+    pub fn try_lock(&self) -> bool {
+        unsafe { crate::shmem_test_lock(self.raw()) == 0 }
+    }
+
+    /// takes the lock and returns a guard that releases it when dropped
+    pub fn guard(&self) -> LockGuard<'_> {
+        self.lock();
+        LockGuard { lock: self }
+    }
+
+    /// like [`guard`](DistributedLock::guard), `None` if another PE holds the lock
+    pub fn try_guard(&self) -> Option<LockGuard<'_>> {
+        self.try_lock().then_some(LockGuard { lock: self })
+    }
+}
+
+/// Holds a [`DistributedLock`] until dropped
+pub struct LockGuard<'a> {
+    lock: &'a DistributedLock,
+}
+
+impl Drop for LockGuard<'_> {
+    fn drop(&mut self) {
+        self.lock.unlock();
+    }
+}
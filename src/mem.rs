@@ -0,0 +1,302 @@
+//! Symmetric heap allocations with RAII cleanup.
+
+use std::ffi::c_void;
+use std::fmt;
+use std::mem;
+#[cfg(shmem_1_5)]
+use std::ops::{BitOr, BitOrAssign};
+use std::ops::{Deref, DerefMut};
+#[cfg(shmem_1_5)]
+use std::os::raw::c_long;
+use std::ptr::{self, NonNull};
+use std::sync::OnceLock;
+
+use crate::ShmemError;
+#[cfg(shmem_1_5)]
+use crate::consts;
+
+/// the symmetric heap size requested through `SHMEM_SYMMETRIC_SIZE`, in bytes
+///
+/// The library reads the variable once, in `shmem_init`, so this is the value it had when
+/// [`Shmem`](crate::Shmem) was initialized (or when this was first called, if that was earlier).
+/// `None` if the variable isn't set or isn't a number with an optional `K`, `M`, `G` or `T`
+/// suffix; the heap then has the implementation's default size, which it doesn't report.
+pub fn symmetric_heap_size() -> Option<usize> {
+    static SIZE: OnceLock<Option<usize>> = OnceLock::new();
+    *SIZE.get_or_init(|| parse_heap_size(&std::env::var("SHMEM_SYMMETRIC_SIZE").ok()?))
+}
+
+/// parses a size like `512M` or `1.5g`, the suffixes are powers of 1024
+fn parse_heap_size(value: &str) -> Option<usize> {
+    let value = value.trim();
+    let (number, scale) = match value.char_indices().last()? {
+        (i, 'k' | 'K') => (&value[..i], 1u64 << 10),
+        (i, 'm' | 'M') => (&value[..i], 1 << 20),
+        (i, 'g' | 'G') => (&value[..i], 1 << 30),
+        (i, 't' | 'T') => (&value[..i], 1 << 40),
+        _ => (value, 1),
+    };
+    if let Ok(number) = number.parse::<u64>() {
+        return usize::try_from(number.checked_mul(scale)?).ok();
+    }
+    let number: f64 = number.parse().ok()?;
+    (number.is_finite() && number >= 0.0).then_some((number * scale as f64) as usize)
+}
+
+/// Hints about how an allocation is used, for [`SymmetricBox::new_with_hints`]
+///
+/// Combine them with `|`. The library may use them to place the memory, they never change what
+/// is allowed on it.
+#[cfg(shmem_1_5)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MallocHints(c_long);
+
+#[cfg(shmem_1_5)]
+impl MallocHints {
+    /// no hints, the same as a plain allocation
+    pub const NONE: MallocHints = MallocHints(0);
+    /// the memory is mostly the target of atomic operations from other PEs
+    pub const ATOMICS_REMOTE: MallocHints = MallocHints(consts::SHMEM_MALLOC_ATOMICS_REMOTE);
+    /// the memory is mostly used as signal words of put-with-signal operations
+    pub const SIGNAL_REMOTE: MallocHints = MallocHints(consts::SHMEM_MALLOC_SIGNAL_REMOTE);
+
+    /// the `SHMEM_MALLOC_*` bits passed to `shmem_malloc_with_hints`
+    pub fn bits(self) -> c_long {
+        self.0
+    }
+
+    /// whether all hints in `other` are set
+    pub fn contains(self, other: MallocHints) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+#[cfg(shmem_1_5)]
+impl BitOr for MallocHints {
+    type Output = MallocHints;
+
+    fn bitor(self, other: MallocHints) -> MallocHints {
+        MallocHints(self.0 | other.0)
+    }
+}
+
+#[cfg(shmem_1_5)]
+impl BitOrAssign for MallocHints {
+    fn bitor_assign(&mut self, other: MallocHints) {
+        self.0 |= other.0;
+    }
+}
+
+/// A value allocated on the symmetric heap, freed with `shmem_free` when dropped
+///
+/// Allocating and freeing are collective: every PE has to create (and drop) its boxes in the
+/// same order so that they end up at the same symmetric address.
+pub struct SymmetricBox<T> {
+    ptr: NonNull<T>,
+}
+
+impl<T> SymmetricBox<T> {
+    /// moves `value` into a new symmetric allocation using `shmem_align`
+    pub fn new(value: T) -> Result<SymmetricBox<T>, ShmemError> {
+        Self::new_in(value, || unsafe {
+            crate::shmem_align(mem::align_of::<T>(), mem::size_of::<T>())
+        })
+    }
+
+    /// moves `value` into a new symmetric allocation using `shmem_malloc_with_hints`
+    ///
+    /// # Panics
+    ///
+    /// If the library returns memory that is not aligned enough for `T`. Like `malloc` it only
+    /// guarantees the alignment of the largest C type.
+    #[cfg(shmem_1_5)]
+    pub fn new_with_hints(value: T, hints: MallocHints) -> Result<SymmetricBox<T>, ShmemError> {
+        Self::new_in(value, || {
+            let raw = unsafe { crate::shmem_malloc_with_hints(mem::size_of::<T>(), hints.bits()) };
+            assert!(
+                raw.cast::<T>().is_aligned(),
+                "shmem_malloc_with_hints returned memory not aligned for {}",
+                std::any::type_name::<T>()
+            );
+            raw
+        })
+    }
+
+    /// moves `value` into the memory returned by `alloc`, which is not called for zero sized
+    /// types
+    fn new_in(
+        value: T,
+        alloc: impl FnOnce() -> *mut c_void,
+    ) -> Result<SymmetricBox<T>, ShmemError> {
+        let ptr = if mem::size_of::<T>() == 0 {
+            // The symmetric heap can't hand out zero sized allocations.
+            NonNull::dangling()
+        } else {
+            NonNull::new(alloc() as *mut T).ok_or(ShmemError::AllocFailed {
+                size: mem::size_of::<T>(),
+            })?
+        };
+        unsafe { ptr.as_ptr().write(value) };
+        Ok(SymmetricBox { ptr })
+    }
+
+    /// the symmetric address of the value, usable as a target for remote operations
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.as_ptr()
+    }
+
+    /// the symmetric address of the value, usable as a target for remote operations
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<T> Deref for SymmetricBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> DerefMut for SymmetricBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SymmetricBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Drop for SymmetricBox<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.ptr.as_ptr());
+            if mem::size_of::<T>() != 0 {
+                crate::shmem_free(self.ptr.as_ptr() as *mut c_void);
+            }
+        }
+    }
+}
+
+/// A fixed length array allocated on the symmetric heap, freed with `shmem_free` when dropped
+///
+/// Like [`SymmetricBox`] allocating and freeing are collective: every PE has to create its
+/// arrays with the same length and alignment, and create and drop them in the same order.
+pub struct SymmetricVec<T> {
+    ptr: NonNull<T>,
+    len: usize,
+}
+
+impl<T> SymmetricVec<T> {
+    /// allocates `len` clones of `value` using `shmem_align`, aligned to at least `align` bytes
+    ///
+    /// # Panics
+    ///
+    /// If `align` is not a power of two or the array would be larger than `isize::MAX` bytes.
+    pub fn from_elem(value: T, len: usize, align: usize) -> Result<SymmetricVec<T>, ShmemError>
+    where
+        T: Clone,
+    {
+        assert!(
+            align.is_power_of_two(),
+            "alignment {} is not a power of two",
+            align
+        );
+        let align = align.max(mem::align_of::<T>());
+        let vec = Self::alloc(len, align, |size| unsafe {
+            crate::shmem_align(align, size)
+        })?;
+        // A panicking clone leaks the allocation, it can't be freed without the other PEs.
+        for i in 0..len {
+            unsafe { vec.ptr.as_ptr().add(i).write(value.clone()) };
+        }
+        Ok(vec)
+    }
+
+    /// allocates `len` zeroed elements using `shmem_calloc`
+    ///
+    /// # Safety
+    ///
+    /// All zero bytes must be a valid `T`.
+    ///
+    /// # Panics
+    ///
+    /// If the library returns memory that is not aligned enough for `T`, or the array would be
+    /// larger than `isize::MAX` bytes.
+    pub unsafe fn zeroed(len: usize) -> Result<SymmetricVec<T>, ShmemError> {
+        Self::alloc(len, mem::align_of::<T>(), |_| {
+            let raw = unsafe { crate::shmem_calloc(len, mem::size_of::<T>()) };
+            assert!(
+                raw.cast::<T>().is_aligned(),
+                "shmem_calloc returned memory not aligned for {}",
+                std::any::type_name::<T>()
+            );
+            raw
+        })
+    }
+
+    /// gets uninitialized memory for `len` elements from `alloc`, which is passed the size in
+    /// bytes and not called for empty arrays
+    fn alloc(
+        len: usize,
+        align: usize,
+        alloc: impl FnOnce(usize) -> *mut c_void,
+    ) -> Result<SymmetricVec<T>, ShmemError> {
+        let size = mem::size_of::<T>()
+            .checked_mul(len)
+            .filter(|&size| size <= isize::MAX as usize)
+            .expect("symmetric array too large");
+        let ptr = if size == 0 {
+            // The symmetric heap can't hand out zero sized allocations.
+            NonNull::new(ptr::without_provenance_mut(align)).unwrap()
+        } else {
+            NonNull::new(alloc(size) as *mut T).ok_or(ShmemError::AllocFailed { size })?
+        };
+        Ok(SymmetricVec { ptr, len })
+    }
+
+    /// the symmetric address of the first element, usable as a target for remote operations
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.as_ptr()
+    }
+
+    /// the symmetric address of the first element, usable as a target for remote operations
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<T> Deref for SymmetricVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for SymmetricVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SymmetricVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Drop for SymmetricVec<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len));
+            if mem::size_of::<T>() != 0 && self.len != 0 {
+                crate::shmem_free(self.ptr.as_ptr() as *mut c_void);
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Team based reductions over the `shmem_<type>_<op>_reduce` routines.
+
+use crate::ShmemError;
+use crate::shmem_team_t;
+use crate::team::Team;
+
+/// A reduction operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReduceOp {
+    Sum,
+    Prod,
+    Min,
+    Max,
+    /// bitwise and, integers only
+    And,
+    /// bitwise or, integers only
+    Or,
+    /// bitwise xor, integers only
+    Xor,
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Types OpenSHMEM can reduce
+///
+/// This is sealed, the library only provides the routines for a fixed set of types.
+pub trait ShmemReducible: Copy + private::Sealed {
+    /// runs the reduction, `None` if the library has no routine for `op` on this type
+    #[doc(hidden)]
+    unsafe fn reduce_raw(
+        team: shmem_team_t,
+        op: ReduceOp,
+        dest: *mut Self,
+        src: *const Self,
+        nreduce: usize,
+    ) -> Option<i32>;
+}
+
+macro_rules! impl_reducible {
+    ($t:ty { $($op:ident => $f:ident),* $(,)? }) => {
+        impl private::Sealed for $t {}
+
+        impl ShmemReducible for $t {
+            unsafe fn reduce_raw(
+                team: shmem_team_t,
+                op: ReduceOp,
+                dest: *mut Self,
+                src: *const Self,
+                nreduce: usize,
+            ) -> Option<i32> {
+                match op {
+                    $(ReduceOp::$op => Some(unsafe {
+                        crate::$f(team, dest as *mut _, src as *const _, nreduce)
+                    }),)*
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_reducible!(i32 {
+    Sum => shmem_int32_sum_reduce,
+    Prod => shmem_int32_prod_reduce,
+    Min => shmem_int32_min_reduce,
+    Max => shmem_int32_max_reduce,
+    And => shmem_int32_and_reduce,
+    Or => shmem_int32_or_reduce,
+    Xor => shmem_int32_xor_reduce,
+});
+impl_reducible!(i64 {
+    Sum => shmem_int64_sum_reduce,
+    Prod => shmem_int64_prod_reduce,
+    Min => shmem_int64_min_reduce,
+    Max => shmem_int64_max_reduce,
+    And => shmem_int64_and_reduce,
+    Or => shmem_int64_or_reduce,
+    Xor => shmem_int64_xor_reduce,
+});
+impl_reducible!(u32 {
+    Sum => shmem_uint32_sum_reduce,
+    Prod => shmem_uint32_prod_reduce,
+    Min => shmem_uint32_min_reduce,
+    Max => shmem_uint32_max_reduce,
+    And => shmem_uint32_and_reduce,
+    Or => shmem_uint32_or_reduce,
+    Xor => shmem_uint32_xor_reduce,
+});
+impl_reducible!(u64 {
+    Sum => shmem_uint64_sum_reduce,
+    Prod => shmem_uint64_prod_reduce,
+    Min => shmem_uint64_min_reduce,
+    Max => shmem_uint64_max_reduce,
+    And => shmem_uint64_and_reduce,
+    Or => shmem_uint64_or_reduce,
+    Xor => shmem_uint64_xor_reduce,
+});
+impl_reducible!(f32 {
+    Sum => shmem_float_sum_reduce,
+    Prod => shmem_float_prod_reduce,
+    Min => shmem_float_min_reduce,
+    Max => shmem_float_max_reduce,
+});
+impl_reducible!(f64 {
+    Sum => shmem_double_sum_reduce,
+    Prod => shmem_double_prod_reduce,
+    Min => shmem_double_min_reduce,
+    Max => shmem_double_max_reduce,
+});
+
+/// reduces `src` elementwise over all members of `team` into `dest` on every member
+///
+/// Both buffers must be symmetric memory and every member has to make the same call. Bitwise
+/// operators on floating point types return [`ShmemError::UnsupportedReduction`].
+///
+/// # Panics
+///
+/// If `dest` and `src` differ in length.
+pub fn reduce<T: ShmemReducible>(
+    team: &Team,
+    op: ReduceOp,
+    dest: &mut [T],
+    src: &[T],
+) -> Result<(), ShmemError> {
+    assert_eq!(dest.len(), src.len(), "reduce: length mismatch");
+    let status = unsafe {
+        T::reduce_raw(
+            team.as_raw(),
+            op,
+            dest.as_mut_ptr(),
+            src.as_ptr(),
+            src.len(),
+        )
+    };
+    match status {
+        None => Err(ShmemError::UnsupportedReduction {
+            op,
+            ty: std::any::type_name::<T>(),
+        }),
+        Some(0) => Ok(()),
+        Some(status) => Err(ShmemError::CollectiveFailed(status)),
+    }
+}
@@ -0,0 +1,49 @@
+//! Typed remote memory access over `shmem_putmem`/`shmem_getmem`.
+
+use std::ffi::c_void;
+use std::mem;
+
+pub mod nonblocking;
+pub mod scalar;
+pub mod strided;
+
+/// copies `src` into `dest` on PE `pe`
+///
+/// `dest` must be symmetric memory (e.g. allocated with `shmem_malloc`); it names the remote
+/// object that sits at the same address on `pe`. The call returns once `src` can be reused, not
+/// when the data has arrived, see `shmem_quiet`.
+///
+/// # Panics
+///
+/// If `dest` and `src` differ in length.
+pub fn put<T: Copy>(dest: &mut [T], src: &[T], pe: i32) {
+    assert_eq!(dest.len(), src.len(), "put: length mismatch");
+    unsafe {
+        crate::shmem_putmem(
+            dest.as_mut_ptr() as *mut c_void,
+            src.as_ptr() as *const c_void,
+            mem::size_of_val(src),
+            pe,
+        )
+    };
+}
+
+/// copies `src` on PE `pe` into `dest`
+///
+/// `src` must be symmetric memory (e.g. allocated with `shmem_malloc`); it names the remote
+/// object that sits at the same address on `pe`. `dest` holds the data when the call returns.
+///
+/// # Panics
+///
+/// If `dest` and `src` differ in length.
+pub fn get<T: Copy>(dest: &mut [T], src: &[T], pe: i32) {
+    assert_eq!(dest.len(), src.len(), "get: length mismatch");
+    unsafe {
+        crate::shmem_getmem(
+            dest.as_mut_ptr() as *mut c_void,
+            src.as_ptr() as *const c_void,
+            mem::size_of_val(src),
+            pe,
+        )
+    };
+}
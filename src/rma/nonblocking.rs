@@ -0,0 +1,209 @@
+//! Non-blocking remote memory access over `shmem_putmem_nbi`/`shmem_getmem_nbi`.
+//!
+//! These take the same arguments as the blocking [`put`](super::put) and [`get`](super::get),
+//! but may return before the transfer has even started. Neither buffer may be touched until a
+//! later [`quiet`](crate::sync::quiet) has completed it, which the borrow checker can't see, so
+//! [`put_nbi`] and [`get_nbi`] are `unsafe`. [`nbi_scope`] and [`prefetch`] are the safe way to
+//! use them.
+
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::mem;
+
+use crate::sync::quiet;
+
+/// starts copying `src` into `dest` on PE `pe`
+///
+/// `dest` must be symmetric memory.
+///
+/// # Safety
+///
+/// The transfer is still in flight when this returns. Until the next `shmem_quiet` on this PE,
+/// `src` must not be written or freed, and `dest` must not be accessed or freed.
+///
+/// # Panics
+///
+/// If `dest` and `src` differ in length.
+pub unsafe fn put_nbi<T: Copy>(dest: &mut [T], src: &[T], pe: i32) {
+    assert_eq!(dest.len(), src.len(), "put_nbi: length mismatch");
+    unsafe {
+        crate::shmem_putmem_nbi(
+            dest.as_mut_ptr() as *mut c_void,
+            src.as_ptr() as *const c_void,
+            mem::size_of_val(src),
+            pe,
+        )
+    };
+}
+
+/// starts copying `src` on PE `pe` into `dest`
+///
+/// `src` must be symmetric memory. `dest` only holds the data after the next `quiet`.
+///
+/// # Safety
+///
+/// The transfer is still in flight when this returns. Until the next `shmem_quiet` on this PE,
+/// `dest` must not be accessed or freed, and `src` must not be freed.
+///
+/// # Panics
+///
+/// If `dest` and `src` differ in length.
+pub unsafe fn get_nbi<T: Copy>(dest: &mut [T], src: &[T], pe: i32) {
+    assert_eq!(dest.len(), src.len(), "get_nbi: length mismatch");
+    unsafe {
+        crate::shmem_getmem_nbi(
+            dest.as_mut_ptr() as *mut c_void,
+            src.as_ptr() as *const c_void,
+            mem::size_of_val(src),
+            pe,
+        )
+    };
+}
+
+/// runs `f` with a [`NbiBatch`] and completes every transfer it started before returning
+///
+/// The transfers are completed even if `f` panics. Since the batch only exists inside the call,
+/// there is no way to skip that, e.g. by leaking the batch.
+pub fn nbi_scope<'a, R>(f: impl FnOnce(&mut NbiBatch<'a>) -> R) -> R {
+    let mut batch = NbiBatch {
+        issued: 0,
+        buffers: PhantomData,
+    };
+    f(&mut batch)
+}
+
+/// A group of non-blocking transfers completed together, see [`nbi_scope`]
+///
+/// The batch borrows the buffers of every transfer it starts for the whole scope, so they can't
+/// be touched while a transfer might still be in flight. OpenSHMEM can't complete single
+/// transfers, [`complete`](NbiBatch::complete) waits for all of them with `quiet`, which also
+/// completes anything else this PE has issued.
+///
+/// The end of the scope completes the batch as well. In debug builds that prints a warning if
+/// transfers were still outstanding, since it usually means a `complete` call was forgotten.
+#[derive(Debug)]
+pub struct NbiBatch<'a> {
+    issued: usize,
+    buffers: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> NbiBatch<'a> {
+    /// starts a [`put_nbi`] as part of the batch
+    pub fn put<T: Copy>(&mut self, dest: &'a mut [T], src: &'a [T], pe: i32) {
+        // The buffers stay borrowed for `'a`, and `nbi_scope` quiets before it returns.
+        unsafe { put_nbi(dest, src, pe) };
+        self.issued += 1;
+    }
+
+    /// starts a [`get_nbi`] as part of the batch
+    pub fn get<T: Copy>(&mut self, dest: &'a mut [T], src: &'a [T], pe: i32) {
+        unsafe { get_nbi(dest, src, pe) };
+        self.issued += 1;
+    }
+
+    /// the number of transfers started and not yet completed
+    pub fn outstanding(&self) -> usize {
+        self.issued
+    }
+
+    /// waits for every transfer of the batch
+    ///
+    /// The buffers stay borrowed until the end of the scope, more transfers can be started
+    /// after this.
+    pub fn complete(&mut self) {
+        quiet();
+        self.issued = 0;
+    }
+}
+
+impl Drop for NbiBatch<'_> {
+    fn drop(&mut self) {
+        if self.issued == 0 {
+            return;
+        }
+        // A panic in the scope skipping `complete` isn't worth a warning of its own.
+        if cfg!(debug_assertions) && !std::thread::panicking() {
+            eprintln!(
+                "warning: NbiBatch dropped with {} outstanding transfers, completing them",
+                self.issued
+            );
+        }
+        quiet();
+    }
+}
+
+/// runs `f` with a [`Prefetcher`] into `buffer`, then completes every fetch and returns the
+/// filled buffer
+///
+/// The gets are completed even if `f` panics, and since the prefetcher only exists inside the
+/// call, leaking it can't skip that either.
+pub fn prefetch<'a, T: Copy>(
+    buffer: &'a mut [T],
+    f: impl FnOnce(&mut Prefetcher<'a, T>),
+) -> &'a mut [T] {
+    let mut prefetcher = Prefetcher {
+        buffer,
+        fetched: Vec::new(),
+    };
+    f(&mut prefetcher);
+    prefetcher.wait();
+    mem::take(&mut prefetcher.buffer)
+}
+
+/// Gathers data from other PEs into one local buffer with non-blocking gets, see [`prefetch`]
+///
+/// Each [`fetch`](Prefetcher::fetch) starts a `get_nbi` into a part of the buffer, and
+/// [`wait`](Prefetcher::wait) or the end of the scope completes all of them at once with
+/// `quiet`. The buffer only needs to be local
+/// memory, the sources must be symmetric.
+///
+/// Debug builds check that no two fetches write the same part of the buffer.
+#[derive(Debug)]
+pub struct Prefetcher<'a, T> {
+    buffer: &'a mut [T],
+    fetched: Vec<(usize, usize)>,
+}
+
+impl<'a, T: Copy> Prefetcher<'a, T> {
+    /// starts getting `src` from PE `pe` into the buffer, starting at element `at`
+    ///
+    /// # Panics
+    ///
+    /// If `src` doesn't fit into the buffer at `at`, or in debug builds if it overlaps a fetch
+    /// not yet waited for.
+    pub fn fetch(&mut self, at: usize, src: &'a [T], pe: i32) {
+        let end = at + src.len();
+        debug_assert!(
+            self.fetched
+                .iter()
+                .all(|&(start, stop)| end <= start || stop <= at),
+            "Prefetcher: elements {}..{} overlap an earlier fetch",
+            at,
+            end
+        );
+        // The buffer and `src` stay borrowed until `prefetch` or the drop quiets.
+        unsafe { get_nbi(&mut self.buffer[at..end], src, pe) };
+        self.fetched.push((at, end));
+    }
+
+    /// the number of gets started and not yet completed
+    pub fn outstanding(&self) -> usize {
+        self.fetched.len()
+    }
+
+    /// completes every fetch so far, e.g. to start more into the same parts of the buffer
+    ///
+    /// The filled buffer is only handed back at the end of the scope.
+    pub fn wait(&mut self) {
+        quiet();
+        self.fetched.clear();
+    }
+}
+
+impl<T> Drop for Prefetcher<'_, T> {
+    fn drop(&mut self) {
+        if !self.fetched.is_empty() {
+            quiet();
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Single element remote memory access over the `shmem_<type>_p`/`shmem_<type>_g` routines.
+//!
+//! These move one value without the length bookkeeping of the slice routines, which makes them
+//! the cheapest way to update a flag or read a counter on another PE.
+
+use std::os::raw::{c_int, c_longlong};
+#[cfg(shmem_1_4)]
+use std::os::raw::{c_uint, c_ulonglong};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Element types with single element OpenSHMEM transfers
+///
+/// This is sealed, the library only provides the routines for a fixed set of types. `u32` and
+/// `u64` need a 1.4 library.
+pub trait ShmemScalar: Copy + private::Sealed {
+    #[doc(hidden)]
+    unsafe fn p(dest: *mut Self, value: Self, pe: i32);
+    #[doc(hidden)]
+    unsafe fn g(src: *const Self, pe: i32) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($t:ty, $c:ty, $p:ident, $g:ident) => {
+        impl private::Sealed for $t {}
+
+        impl ShmemScalar for $t {
+            unsafe fn p(dest: *mut Self, value: Self, pe: i32) {
+                unsafe { crate::$p(dest as *mut $c, value as $c, pe) }
+            }
+
+            unsafe fn g(src: *const Self, pe: i32) -> Self {
+                unsafe { crate::$g(src as *const $c, pe) as $t }
+            }
+        }
+    };
+}
+
+impl_scalar!(i32, c_int, shmem_int_p, shmem_int_g);
+#[cfg(shmem_1_4)]
+impl_scalar!(u32, c_uint, shmem_uint_p, shmem_uint_g);
+impl_scalar!(i64, c_longlong, shmem_longlong_p, shmem_longlong_g);
+#[cfg(shmem_1_4)]
+impl_scalar!(u64, c_ulonglong, shmem_ulonglong_p, shmem_ulonglong_g);
+impl_scalar!(f32, f32, shmem_float_p, shmem_float_g);
+impl_scalar!(f64, f64, shmem_double_p, shmem_double_g);
+
+/// writes `value` into `dest` on PE `pe`
+///
+/// `dest` must be symmetric; it names the remote object at the same address on `pe`. Like
+/// [`put`](super::put) the value may not have arrived when this returns, see `shmem_quiet`.
+pub fn put_scalar<T: ShmemScalar>(dest: &mut T, value: T, pe: i32) {
+    unsafe { T::p(dest, value, pe) };
+}
+
+/// reads `src` on PE `pe`
+///
+/// `src` must be symmetric; it names the remote object at the same address on `pe`.
+pub fn get_scalar<T: ShmemScalar>(src: &T, pe: i32) -> T {
+    unsafe { T::g(src, pe) }
+}
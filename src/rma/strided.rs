@@ -0,0 +1,184 @@
+//! Strided remote memory access over the `shmem_<type>_iput`/`shmem_<type>_iget` routines.
+//!
+//! These move `nelems` elements, reading every `src_stride`th element of the source and writing
+//! every `dst_stride`th element of the destination. A stride of 1 is a contiguous transfer.
+
+use std::os::raw::{c_int, c_longlong};
+#[cfg(shmem_1_4)]
+use std::os::raw::{c_uint, c_ulonglong};
+
+use crate::ptrdiff_t;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Element types with strided OpenSHMEM transfers
+///
+/// This is sealed, the library only provides the routines for a fixed set of types. `u32` and
+/// `u64` need a 1.4 library.
+pub trait ShmemStrided: Copy + private::Sealed {
+    #[doc(hidden)]
+    unsafe fn iput(
+        dest: *mut Self,
+        src: *const Self,
+        dst_stride: ptrdiff_t,
+        src_stride: ptrdiff_t,
+        nelems: usize,
+        pe: i32,
+    );
+    #[doc(hidden)]
+    unsafe fn iget(
+        dest: *mut Self,
+        src: *const Self,
+        dst_stride: ptrdiff_t,
+        src_stride: ptrdiff_t,
+        nelems: usize,
+        pe: i32,
+    );
+}
+
+macro_rules! impl_strided {
+    ($t:ty, $c:ty, $iput:ident, $iget:ident) => {
+        impl private::Sealed for $t {}
+
+        impl ShmemStrided for $t {
+            unsafe fn iput(
+                dest: *mut Self,
+                src: *const Self,
+                dst_stride: ptrdiff_t,
+                src_stride: ptrdiff_t,
+                nelems: usize,
+                pe: i32,
+            ) {
+                unsafe {
+                    crate::$iput(
+                        dest as *mut $c,
+                        src as *const $c,
+                        dst_stride,
+                        src_stride,
+                        nelems,
+                        pe,
+                    )
+                }
+            }
+
+            unsafe fn iget(
+                dest: *mut Self,
+                src: *const Self,
+                dst_stride: ptrdiff_t,
+                src_stride: ptrdiff_t,
+                nelems: usize,
+                pe: i32,
+            ) {
+                unsafe {
+                    crate::$iget(
+                        dest as *mut $c,
+                        src as *const $c,
+                        dst_stride,
+                        src_stride,
+                        nelems,
+                        pe,
+                    )
+                }
+            }
+        }
+    };
+}
+
+impl_strided!(i32, c_int, shmem_int_iput, shmem_int_iget);
+#[cfg(shmem_1_4)]
+impl_strided!(u32, c_uint, shmem_uint_iput, shmem_uint_iget);
+impl_strided!(i64, c_longlong, shmem_longlong_iput, shmem_longlong_iget);
+#[cfg(shmem_1_4)]
+impl_strided!(u64, c_ulonglong, shmem_ulonglong_iput, shmem_ulonglong_iget);
+impl_strided!(f32, f32, shmem_float_iput, shmem_float_iget);
+impl_strided!(f64, f64, shmem_double_iput, shmem_double_iget);
+
+/// asserts that `nelems` elements `stride` apart fit into `len`, returning the stride for the C
+/// routines
+pub(crate) fn checked_stride(what: &str, len: usize, stride: usize, nelems: usize) -> ptrdiff_t {
+    assert!(stride > 0, "{}: stride must not be 0", what);
+    if nelems > 0 {
+        let needed = (nelems - 1)
+            .checked_mul(stride)
+            .and_then(|last| last.checked_add(1))
+            .unwrap_or_else(|| {
+                panic!(
+                    "{}: {} elements of stride {} overflow",
+                    what, nelems, stride
+                )
+            });
+        assert!(
+            needed <= len,
+            "{}: {} elements of stride {} need {} slots, the slice has {}",
+            what,
+            nelems,
+            stride,
+            needed,
+            len
+        );
+    }
+    ptrdiff_t::try_from(stride).expect("stride does not fit in ptrdiff_t")
+}
+
+/// copies `nelems` elements of `src`, `src_stride` apart, into `dest` on PE `pe`, `dst_stride`
+/// apart
+///
+/// `dest` must be symmetric memory. Like [`put`](super::put) this returns once `src` can be
+/// reused.
+///
+/// # Panics
+///
+/// If a stride is 0 or either slice is shorter than `(nelems - 1) * stride + 1`.
+pub fn iput<T: ShmemStrided>(
+    dest: &mut [T],
+    src: &[T],
+    dst_stride: usize,
+    src_stride: usize,
+    nelems: usize,
+    pe: i32,
+) {
+    let dst_stride = checked_stride("iput dest", dest.len(), dst_stride, nelems);
+    let src_stride = checked_stride("iput src", src.len(), src_stride, nelems);
+    unsafe {
+        T::iput(
+            dest.as_mut_ptr(),
+            src.as_ptr(),
+            dst_stride,
+            src_stride,
+            nelems,
+            pe,
+        )
+    };
+}
+
+/// copies `nelems` elements of `src` on PE `pe`, `src_stride` apart, into `dest`, `dst_stride`
+/// apart
+///
+/// `src` must be symmetric memory. `dest` holds the data when the call returns.
+///
+/// # Panics
+///
+/// If a stride is 0 or either slice is shorter than `(nelems - 1) * stride + 1`.
+pub fn iget<T: ShmemStrided>(
+    dest: &mut [T],
+    src: &[T],
+    dst_stride: usize,
+    src_stride: usize,
+    nelems: usize,
+    pe: i32,
+) {
+    let dst_stride = checked_stride("iget dest", dest.len(), dst_stride, nelems);
+    let src_stride = checked_stride("iget src", src.len(), src_stride, nelems);
+    unsafe {
+        T::iget(
+            dest.as_mut_ptr(),
+            src.as_ptr(),
+            dst_stride,
+            src_stride,
+            nelems,
+            pe,
+        )
+    };
+}
@@ -0,0 +1,131 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::ShmemError;
+#[cfg(shmem_1_4)]
+use crate::consts;
+
+/// Set by the first successful initialization. OpenSHMEM can't be initialized again after
+/// `shmem_finalize`, so this is never reset.
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// The thread safety levels accepted by `shmem_init_thread`, from least to most permissive
+#[cfg(shmem_1_4)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ThreadLevel {
+    /// only one thread exists in the process
+    Single,
+    /// only the thread that initialized the runtime makes OpenSHMEM calls
+    Funneled,
+    /// OpenSHMEM calls are made by multiple threads, but never at the same time
+    Serialized,
+    /// any thread may make OpenSHMEM calls at any time
+    Multiple,
+}
+
+#[cfg(shmem_1_4)]
+impl ThreadLevel {
+    const ALL: [ThreadLevel; 4] = [
+        ThreadLevel::Single,
+        ThreadLevel::Funneled,
+        ThreadLevel::Serialized,
+        ThreadLevel::Multiple,
+    ];
+
+    /// the matching `SHMEM_THREAD_*` constant
+    pub fn as_raw(self) -> i32 {
+        match self {
+            ThreadLevel::Single => consts::SHMEM_THREAD_SINGLE,
+            ThreadLevel::Funneled => consts::SHMEM_THREAD_FUNNELED,
+            ThreadLevel::Serialized => consts::SHMEM_THREAD_SERIALIZED,
+            ThreadLevel::Multiple => consts::SHMEM_THREAD_MULTIPLE,
+        }
+    }
+
+    /// the level for a `SHMEM_THREAD_*` constant
+    pub fn from_raw(level: i32) -> Option<ThreadLevel> {
+        Self::ALL.into_iter().find(|l| l.as_raw() == level)
+    }
+}
+
+/// An initialized OpenSHMEM runtime, finalized when dropped
+///
+/// Only one `Shmem` can ever be created per process.
+#[derive(Debug)]
+pub struct Shmem {
+    _priv: (),
+}
+
+impl Shmem {
+    /// calls `shmem_init`
+    pub fn init() -> Result<Shmem, ShmemError> {
+        Self::claim()?;
+        // Remember the heap size the library is about to read.
+        crate::mem::symmetric_heap_size();
+        unsafe { crate::shmem_init() };
+        Ok(Shmem { _priv: () })
+    }
+
+    /// calls `shmem_init_thread`, returning the thread level the library actually provides
+    ///
+    /// The granted level may be lower than `requested`. If the library reports a level this crate
+    /// doesn't know, the runtime is finalized again and this fails with
+    /// [`ShmemError::UnknownThreadLevel`].
+    #[cfg(shmem_1_4)]
+    pub fn init_with_threads(requested: ThreadLevel) -> Result<(ThreadLevel, Shmem), ShmemError> {
+        Self::claim()?;
+        // Remember the heap size the library is about to read.
+        crate::mem::symmetric_heap_size();
+
+        let mut provided = 0;
+        let status = unsafe { crate::shmem_init_thread(requested.as_raw(), &mut provided) };
+        if status != 0 {
+            // Nothing was initialized, so a later attempt is allowed.
+            INITIALIZED.store(false, Ordering::Release);
+            return Err(ShmemError::InitFailed(status));
+        }
+
+        let shmem = Shmem { _priv: () };
+        let Some(provided) = ThreadLevel::from_raw(provided) else {
+            // Dropping `shmem` finalizes the runtime again.
+            return Err(ShmemError::UnknownThreadLevel(provided));
+        };
+        Ok((provided, shmem))
+    }
+
+    /// like [`Shmem::init_with_threads`], but fails unless at least `required` is provided
+    ///
+    /// The runtime has already been initialized (and is finalized again) when this fails, so the
+    /// program can't retry with a lower level.
+    #[cfg(shmem_1_4)]
+    pub fn init_requiring_threads(required: ThreadLevel) -> Result<Shmem, ShmemError> {
+        let (provided, shmem) = Self::init_with_threads(required)?;
+        if provided < required {
+            return Err(ShmemError::ThreadLevelUnsupported { required, provided });
+        }
+        Ok(shmem)
+    }
+
+    /// marks the runtime as initialized, failing if it already was
+    fn claim() -> Result<(), ShmemError> {
+        INITIALIZED
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .map(|_| ())
+            .map_err(|_| ShmemError::AlreadyInitialized)
+    }
+
+    /// the number of the calling PE
+    pub fn my_pe(&self) -> i32 {
+        unsafe { crate::shmem_my_pe() }
+    }
+
+    /// the number of PEs running the program
+    pub fn n_pes(&self) -> i32 {
+        unsafe { crate::shmem_n_pes() }
+    }
+}
+
+impl Drop for Shmem {
+    fn drop(&mut self) {
+        unsafe { crate::shmem_finalize() };
+    }
+}
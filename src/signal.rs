@@ -0,0 +1,376 @@
+//! Point-to-point synchronization over the `shmem_<type>_wait_until` and `shmem_<type>_test`
+//! routines, and with the `shmem-1-5` feature and a 1.5 library the put-with-signal routines and
+//! the `shmem_<type>_wait_until_{all,any,some}` routines waiting on several variables at once.
+//!
+//! `test` and waiting on `u32` and `u64` need a 1.4 library, 1.3 only waits on signed integers.
+
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+use std::ffi::c_void;
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+use std::mem;
+use std::os::raw::{c_int, c_longlong};
+#[cfg(shmem_1_4)]
+use std::os::raw::{c_uint, c_ulonglong};
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+use std::ptr;
+
+use crate::consts;
+
+/// A comparison between a symmetric variable and a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cmp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Cmp {
+    /// the matching `SHMEM_CMP_*` constant
+    pub fn as_raw(self) -> i32 {
+        match self {
+            Cmp::Eq => consts::SHMEM_CMP_EQ,
+            Cmp::Ne => consts::SHMEM_CMP_NE,
+            Cmp::Gt => consts::SHMEM_CMP_GT,
+            Cmp::Ge => consts::SHMEM_CMP_GE,
+            Cmp::Lt => consts::SHMEM_CMP_LT,
+            Cmp::Le => consts::SHMEM_CMP_LE,
+        }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Integer types OpenSHMEM can wait on
+///
+/// This is sealed, the library only provides the routines for a fixed set of types.
+pub trait ShmemWaitable: Copy + private::Sealed {
+    #[doc(hidden)]
+    unsafe fn wait_until(var: *mut Self, cmp: i32, value: Self);
+    #[cfg(shmem_1_4)]
+    #[doc(hidden)]
+    unsafe fn test(var: *mut Self, cmp: i32, value: Self) -> bool;
+    #[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+    #[doc(hidden)]
+    unsafe fn wait_until_all(
+        vars: *mut Self,
+        nelems: usize,
+        status: *const c_int,
+        cmp: i32,
+        value: Self,
+    );
+    #[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+    #[doc(hidden)]
+    unsafe fn wait_until_any(
+        vars: *mut Self,
+        nelems: usize,
+        status: *const c_int,
+        cmp: i32,
+        value: Self,
+    ) -> usize;
+    #[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+    #[doc(hidden)]
+    unsafe fn wait_until_some(
+        vars: *mut Self,
+        nelems: usize,
+        indices: *mut usize,
+        status: *const c_int,
+        cmp: i32,
+        value: Self,
+    ) -> usize;
+}
+
+macro_rules! impl_waitable {
+    (
+        $t:ty,
+        $c:ty,
+        $wait_until:ident,
+        $test:ident,
+        $wait_until_all:ident,
+        $wait_until_any:ident,
+        $wait_until_some:ident
+    ) => {
+        impl private::Sealed for $t {}
+
+        impl ShmemWaitable for $t {
+            unsafe fn wait_until(var: *mut Self, cmp: i32, value: Self) {
+                unsafe { crate::$wait_until(var as *mut $c, cmp, value as $c) }
+            }
+
+            #[cfg(shmem_1_4)]
+            unsafe fn test(var: *mut Self, cmp: i32, value: Self) -> bool {
+                unsafe { crate::$test(var as *mut $c, cmp, value as $c) != 0 }
+            }
+
+            #[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+            unsafe fn wait_until_all(
+                vars: *mut Self,
+                nelems: usize,
+                status: *const c_int,
+                cmp: i32,
+                value: Self,
+            ) {
+                unsafe { crate::$wait_until_all(vars as *mut $c, nelems, status, cmp, value as $c) }
+            }
+
+            #[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+            unsafe fn wait_until_any(
+                vars: *mut Self,
+                nelems: usize,
+                status: *const c_int,
+                cmp: i32,
+                value: Self,
+            ) -> usize {
+                unsafe { crate::$wait_until_any(vars as *mut $c, nelems, status, cmp, value as $c) }
+            }
+
+            #[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+            unsafe fn wait_until_some(
+                vars: *mut Self,
+                nelems: usize,
+                indices: *mut usize,
+                status: *const c_int,
+                cmp: i32,
+                value: Self,
+            ) -> usize {
+                unsafe {
+                    crate::$wait_until_some(
+                        vars as *mut $c,
+                        nelems,
+                        indices,
+                        status,
+                        cmp,
+                        value as $c,
+                    )
+                }
+            }
+        }
+    };
+}
+
+impl_waitable!(
+    i32,
+    c_int,
+    shmem_int_wait_until,
+    shmem_int_test,
+    shmem_int_wait_until_all,
+    shmem_int_wait_until_any,
+    shmem_int_wait_until_some
+);
+#[cfg(shmem_1_4)]
+impl_waitable!(
+    u32,
+    c_uint,
+    shmem_uint_wait_until,
+    shmem_uint_test,
+    shmem_uint_wait_until_all,
+    shmem_uint_wait_until_any,
+    shmem_uint_wait_until_some
+);
+impl_waitable!(
+    i64,
+    c_longlong,
+    shmem_longlong_wait_until,
+    shmem_longlong_test,
+    shmem_longlong_wait_until_all,
+    shmem_longlong_wait_until_any,
+    shmem_longlong_wait_until_some
+);
+#[cfg(shmem_1_4)]
+impl_waitable!(
+    u64,
+    c_ulonglong,
+    shmem_ulonglong_wait_until,
+    shmem_ulonglong_test,
+    shmem_ulonglong_wait_until_all,
+    shmem_ulonglong_wait_until_any,
+    shmem_ulonglong_wait_until_some
+);
+
+/// blocks until the symmetric `var` on this PE, updated by other PEs, compares to `value` as
+/// `cmp` says
+pub fn wait_until<T: ShmemWaitable>(var: &mut T, cmp: Cmp, value: T) {
+    unsafe { T::wait_until(var, cmp.as_raw(), value) }
+}
+
+/// whether the symmetric `var` on this PE currently compares to `value` as `cmp` says
+#[cfg(shmem_1_4)]
+pub fn test<T: ShmemWaitable>(var: &mut T, cmp: Cmp, value: T) -> bool {
+    unsafe { T::test(var, cmp.as_raw(), value) }
+}
+
+/// converts the `exclude` mask of the `wait_until_*` wrappers into the C `status` array, nonzero
+/// for the elements to ignore
+///
+/// # Panics
+///
+/// If `exclude` is not as long as `len`.
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+fn status(what: &str, exclude: Option<&[bool]>, len: usize) -> Option<Vec<c_int>> {
+    exclude.map(|exclude| {
+        assert_eq!(exclude.len(), len, "{}: mask length mismatch", what);
+        exclude.iter().map(|&skip| c_int::from(skip)).collect()
+    })
+}
+
+/// blocks until every symmetric variable in `vars` on this PE compares to `value` as `cmp` says
+///
+/// Elements whose entry in `exclude` is `true` are ignored.
+///
+/// # Panics
+///
+/// If `exclude` differs in length from `vars`.
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+pub fn wait_until_all<T: ShmemWaitable>(
+    vars: &mut [T],
+    cmp: Cmp,
+    value: T,
+    exclude: Option<&[bool]>,
+) {
+    let status = status("wait_until_all", exclude, vars.len());
+    let status_ptr = status
+        .as_ref()
+        .map_or(ptr::null(), |status| status.as_ptr());
+    unsafe {
+        T::wait_until_all(
+            vars.as_mut_ptr(),
+            vars.len(),
+            status_ptr,
+            cmp.as_raw(),
+            value,
+        )
+    };
+}
+
+/// blocks until any symmetric variable in `vars` on this PE compares to `value` as `cmp` says,
+/// returning its index
+///
+/// Elements whose entry in `exclude` is `true` are ignored. `None` if every element is excluded
+/// or `vars` is empty, in which case the call doesn't block.
+///
+/// # Panics
+///
+/// If `exclude` differs in length from `vars`.
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+pub fn wait_until_any<T: ShmemWaitable>(
+    vars: &mut [T],
+    cmp: Cmp,
+    value: T,
+    exclude: Option<&[bool]>,
+) -> Option<usize> {
+    let status = status("wait_until_any", exclude, vars.len());
+    let status_ptr = status
+        .as_ref()
+        .map_or(ptr::null(), |status| status.as_ptr());
+    let index = unsafe {
+        T::wait_until_any(
+            vars.as_mut_ptr(),
+            vars.len(),
+            status_ptr,
+            cmp.as_raw(),
+            value,
+        )
+    };
+    // The library returns `SIZE_MAX` when there was nothing to wait for.
+    (index < vars.len()).then_some(index)
+}
+
+/// blocks until at least one symmetric variable in `vars` on this PE compares to `value` as
+/// `cmp` says, returning the indices of all that do
+///
+/// The spec doesn't fix the order of the indices, sort them if it matters.
+///
+/// Elements whose entry in `exclude` is `true` are ignored. The result is empty if every
+/// element is excluded or `vars` is empty, in which case the call doesn't block.
+///
+/// # Panics
+///
+/// If `exclude` differs in length from `vars`.
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+pub fn wait_until_some<T: ShmemWaitable>(
+    vars: &mut [T],
+    cmp: Cmp,
+    value: T,
+    exclude: Option<&[bool]>,
+) -> Vec<usize> {
+    let status = status("wait_until_some", exclude, vars.len());
+    let status_ptr = status
+        .as_ref()
+        .map_or(ptr::null(), |status| status.as_ptr());
+    let mut indices = vec![0; vars.len()];
+    let found = unsafe {
+        T::wait_until_some(
+            vars.as_mut_ptr(),
+            vars.len(),
+            indices.as_mut_ptr(),
+            status_ptr,
+            cmp.as_raw(),
+            value,
+        )
+    };
+    indices.truncate(found);
+    indices
+}
+
+/// How a put-with-signal updates the signal word
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignalOp {
+    /// replaces the signal with the new value
+    Set,
+    /// adds the new value to the signal
+    Add,
+}
+
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+impl SignalOp {
+    /// the matching `SHMEM_SIGNAL_*` constant
+    pub fn as_raw(self) -> i32 {
+        match self {
+            SignalOp::Set => consts::SHMEM_SIGNAL_SET,
+            SignalOp::Add => consts::SHMEM_SIGNAL_ADD,
+        }
+    }
+}
+
+/// copies `src` into `dest` on PE `pe`, then updates `sig_addr` on PE `pe` with `signal` as
+/// `op` says
+///
+/// The signal update is only visible on `pe` once the data is, so the target can wait on
+/// `sig_addr` instead of a separately fenced flag. `dest` and `sig_addr` must be symmetric.
+///
+/// # Panics
+///
+/// If `dest` and `src` differ in length.
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+pub fn put_signal<T: Copy>(
+    dest: &mut [T],
+    src: &[T],
+    sig_addr: &mut u64,
+    signal: u64,
+    op: SignalOp,
+    pe: i32,
+) {
+    assert_eq!(dest.len(), src.len(), "put_signal: length mismatch");
+    unsafe {
+        crate::shmem_putmem_signal(
+            dest.as_mut_ptr() as *mut c_void,
+            src.as_ptr() as *const c_void,
+            mem::size_of_val(src),
+            sig_addr as *mut _,
+            signal,
+            op.as_raw(),
+            pe,
+        )
+    };
+}
+
+/// reads the symmetric signal word `sig_addr` on this PE
+#[cfg(all(feature = "shmem-1-5", shmem_1_5))]
+pub fn signal_fetch(sig_addr: &u64) -> u64 {
+    unsafe { crate::shmem_signal_fetch(sig_addr as *const _) }
+}
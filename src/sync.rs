@@ -0,0 +1,42 @@
+//! Synchronization and memory ordering.
+//!
+//! `fence` and `quiet` are easy to mix up. [`fence`] only orders: puts issued before it reach
+//! each PE before puts issued after it, but nothing has necessarily arrived when it returns.
+//! [`quiet`] completes: when it returns every put issued before it is visible at its target,
+//! and local source buffers of non-blocking puts may be reused. Use [`order_puts`] to publish
+//! data before a flag on the same PE, and [`complete_puts`] before telling others through some
+//! other channel that the data is there. [`Context::fence`](crate::ctx::Context::fence) and
+//! [`Context::quiet`](crate::ctx::Context::quiet) do the same for the operations of one
+//! context.
+
+/// waits until all PEs arrive, after completing all outstanding remote operations
+pub fn barrier_all() {
+    unsafe { crate::shmem_barrier_all() };
+}
+
+/// waits until all PEs arrive, without completing outstanding remote operations
+#[cfg(shmem_1_4)]
+pub fn sync_all() {
+    unsafe { crate::shmem_sync_all() };
+}
+
+/// orders the puts, AMOs and memory stores issued so far to each PE before any issued later
+pub fn fence() {
+    unsafe { crate::shmem_fence() };
+}
+
+/// waits until all puts, AMOs and memory stores issued so far have completed
+pub fn quiet() {
+    unsafe { crate::shmem_quiet() };
+}
+
+/// [`fence`]: puts issued so far reach each PE before puts issued later, without waiting for
+/// them
+pub fn order_puts() {
+    fence();
+}
+
+/// [`quiet`]: waits until puts issued so far are visible at their targets
+pub fn complete_puts() {
+    quiet();
+}
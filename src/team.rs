@@ -0,0 +1,113 @@
+//! Teams of PEs (`shmem_team_t`).
+
+use std::mem;
+use std::ptr;
+use std::slice;
+
+use crate::{ShmemError, consts, shmem_team_config_t, shmem_team_t};
+
+#[derive(Debug)]
+enum Handle {
+    /// `SHMEM_TEAM_WORLD`, which is owned by the library and never destroyed
+    World,
+    /// `SHMEM_TEAM_SHARED`, which is owned by the library and never destroyed
+    Shared,
+    /// created by a split, destroyed on drop
+    Owned(shmem_team_t),
+}
+
+/// A subset of the PEs, numbered from 0 within the team
+#[derive(Debug)]
+pub struct Team {
+    handle: Handle,
+}
+
+impl Team {
+    /// all PEs of the program
+    pub fn world() -> Team {
+        Team {
+            handle: Handle::World,
+        }
+    }
+
+    /// the PEs that can directly load and store each others symmetric memory
+    pub fn shared() -> Team {
+        Team {
+            handle: Handle::Shared,
+        }
+    }
+
+    /// creates the team of `size` PEs `start, start + stride, ...` (numbered within `parent`)
+    ///
+    /// This is collective over `parent`. PEs that are not part of the new team get
+    /// [`ShmemError::InvalidTeam`].
+    pub fn split_strided(
+        parent: &Team,
+        start: i32,
+        stride: i32,
+        size: i32,
+        config: Option<&shmem_team_config_t>,
+    ) -> Result<Team, ShmemError> {
+        let (config, mask) = match config {
+            Some(config) => (config as *const _, consts::SHMEM_TEAM_NUM_CONTEXTS),
+            None => (ptr::null(), 0),
+        };
+
+        let mut team = unsafe { mem::zeroed() };
+        let status = unsafe {
+            crate::shmem_team_split_strided(
+                parent.as_raw(),
+                start,
+                stride,
+                size,
+                config,
+                mask,
+                &mut team,
+            )
+        };
+        if status != 0 {
+            return Err(ShmemError::TeamSplitFailed(status));
+        }
+        // `SHMEM_TEAM_INVALID` is a null handle in every implementation. Compare the bytes, with
+        // the `opaque-handles` feature the handle isn't a pointer type.
+        let bytes = unsafe {
+            slice::from_raw_parts(
+                &team as *const shmem_team_t as *const u8,
+                mem::size_of_val(&team),
+            )
+        };
+        if bytes.iter().all(|&b| b == 0) {
+            return Err(ShmemError::InvalidTeam);
+        }
+        Ok(Team {
+            handle: Handle::Owned(team),
+        })
+    }
+
+    /// the raw handle, for use with the `shmem_team_*` bindings
+    pub fn as_raw(&self) -> shmem_team_t {
+        match self.handle {
+            Handle::World => unsafe { crate::SHMEM_TEAM_WORLD },
+            Handle::Shared => unsafe { crate::SHMEM_TEAM_SHARED },
+            Handle::Owned(team) => team,
+        }
+    }
+
+    /// the number of the calling PE within the team
+    pub fn my_pe(&self) -> i32 {
+        unsafe { crate::shmem_team_my_pe(self.as_raw()) }
+    }
+
+    /// the number of PEs in the team
+    pub fn n_pes(&self) -> i32 {
+        unsafe { crate::shmem_team_n_pes(self.as_raw()) }
+    }
+}
+
+impl Drop for Team {
+    fn drop(&mut self) {
+        if let Handle::Owned(team) = self.handle {
+            unsafe { crate::shmem_team_destroy(team) };
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//! Wall clock time from the OpenSHMEM runtime.
+
+#[cfg(not(any(shmem_wtime, shmemx_wtime)))]
+use std::sync::OnceLock;
+#[cfg(not(any(shmem_wtime, shmemx_wtime)))]
+use std::time::Instant;
+
+/// seconds since an arbitrary point in the past, from the runtime's own clock
+///
+/// This calls `shmem_wtime` or `shmemx_wtime`, whichever the installed headers declare (see the
+/// `extensions` feature for the latter). If neither does, it measures from the first call within
+/// this process with [`Instant`](std::time::Instant) instead, which is only comparable within the
+/// same PE.
+pub fn wtime() -> f64 {
+    #[cfg(shmem_wtime)]
+    return unsafe { crate::shmem_wtime() };
+    #[cfg(shmemx_wtime)]
+    return unsafe { crate::shmemx_wtime() };
+    #[cfg(not(any(shmem_wtime, shmemx_wtime)))]
+    {
+        static START: OnceLock<Instant> = OnceLock::new();
+        START.get_or_init(Instant::now).elapsed().as_secs_f64()
+    }
+}
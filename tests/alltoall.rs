@@ -0,0 +1,51 @@
+//! Checks that all-to-all exchanges transpose the blocks across PEs.
+//!
+//! With a single PE every block stays in place, run it with at least two.
+
+#![cfg(all(feature = "runtime-tests", shmem_1_5))]
+
+use openshmem_sys::Shmem;
+use openshmem_sys::collectives::{alltoall, alltoalls};
+use openshmem_sys::mem::SymmetricVec;
+use openshmem_sys::sync::barrier_all;
+use openshmem_sys::team::Team;
+
+const BLOCK: usize = 2;
+
+#[test]
+fn alltoall_transposes() {
+    let _shmem = Shmem::init().unwrap();
+    let team = Team::world();
+    let me = team.my_pe() as usize;
+    let n_pes = team.n_pes() as usize;
+    let len = BLOCK * n_pes;
+
+    // Element `k` of block `j` on PE `i` is `(i * n_pes + j) * BLOCK + k`, so after the exchange
+    // block `i` on PE `j` holds what block `j` held on PE `i`.
+    let expected: Vec<i64> = (0..len)
+        .map(|x| ((x / BLOCK * n_pes + me) * BLOCK + x % BLOCK) as i64)
+        .collect();
+
+    let mut src = SymmetricVec::from_elem(0i64, len, 1).unwrap();
+    for (x, v) in src.iter_mut().enumerate() {
+        *v = (me * len + x) as i64;
+    }
+    let mut dest = SymmetricVec::from_elem(-1i64, len, 1).unwrap();
+    barrier_all();
+    alltoall(&team, &mut dest, &src).unwrap();
+    assert_eq!(*dest, expected[..]);
+
+    // The same exchange with every other element, the gaps must stay untouched.
+    let mut strided_src = SymmetricVec::from_elem(0i64, 2 * len - 1, 1).unwrap();
+    for x in 0..len {
+        strided_src[2 * x] = src[x];
+    }
+    let mut strided_dest = SymmetricVec::from_elem(-1i64, 2 * len - 1, 1).unwrap();
+    barrier_all();
+    alltoalls(&team, &mut strided_dest, &strided_src, 2, 2, BLOCK).unwrap();
+    for x in 0..len {
+        assert_eq!(strided_dest[2 * x], expected[x]);
+    }
+    assert!(strided_dest.iter().skip(1).step_by(2).all(|&v| v == -1));
+    barrier_all();
+}
@@ -0,0 +1,43 @@
+//! Checks that the fetching atomics return the value they replaced.
+//!
+//! With a single PE nobody competes for the claim, run it with at least two.
+
+#![cfg(all(feature = "runtime-tests", shmem_1_4))]
+
+use openshmem_sys::Shmem;
+use openshmem_sys::atomic::{atomic_fetch, atomic_fetch_add, atomic_swap, compare_swap};
+use openshmem_sys::mem::SymmetricBox;
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn fetching_atomics_return_prior_value() {
+    let shmem = Shmem::init().unwrap();
+    let me = shmem.my_pe();
+    let n_pes = shmem.n_pes();
+
+    let mut owner = SymmetricBox::new(-1i32).unwrap();
+    let mut counter = SymmetricBox::new(0i64).unwrap();
+    barrier_all();
+
+    // Exactly one PE sees the initial value and wins the claim on PE 0.
+    let seen = compare_swap(&mut *owner, -1, me, 0);
+    assert!(seen == -1 || (0..n_pes).contains(&seen), "saw {}", seen);
+    let before = atomic_fetch_add(&mut *counter, 1, 0);
+    assert!(
+        (0..n_pes as i64).contains(&before),
+        "counter was {}",
+        before
+    );
+    barrier_all();
+
+    let winner = atomic_fetch(&*owner, 0);
+    assert_eq!(seen == -1, winner == me);
+    assert_eq!(atomic_fetch(&*counter, 0), n_pes as i64);
+    barrier_all();
+
+    if me == 0 {
+        assert_eq!(atomic_swap(&mut *counter, 0, 0), n_pes as i64);
+        assert_eq!(atomic_fetch(&*counter, 0), 0);
+    }
+    barrier_all();
+}
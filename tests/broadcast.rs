@@ -0,0 +1,26 @@
+//! Checks that broadcasts reject roots outside the team before calling into the library.
+
+#![cfg(all(feature = "runtime-tests", shmem_1_5))]
+
+use openshmem_sys::collectives::broadcast;
+use openshmem_sys::mem::SymmetricVec;
+use openshmem_sys::team::Team;
+use openshmem_sys::{Shmem, ShmemError};
+
+#[test]
+fn broadcast_root_in_team() {
+    let _shmem = Shmem::init().unwrap();
+    let team = Team::world();
+    let n_pes = team.n_pes();
+    let mut data = SymmetricVec::from_elem(team.my_pe(), 4, 1).unwrap();
+
+    for root in [-1, n_pes, n_pes + 5] {
+        assert_eq!(
+            broadcast(&team, root, &mut data),
+            Err(ShmemError::InvalidRoot { root, n_pes })
+        );
+    }
+    let last = n_pes - 1;
+    broadcast(&team, last, &mut data).unwrap();
+    assert_eq!(*data, [last; 4]);
+}
@@ -0,0 +1,36 @@
+//! Setup shared by the runtime tests.
+//!
+//! Like `tests/runtime.rs` these need a working OpenSHMEM runtime, run them under `oshrun`, e.g.
+//! `CARGO_TARGET_X86_64_UNKNOWN_LINUX_GNU_RUNNER="oshrun -n 2" cargo test --features runtime-tests`.
+//! Every test file is its own process, so each can initialize the runtime once.
+
+// Each test file uses a different part of this.
+#![allow(dead_code)]
+
+use openshmem_sys::Shmem;
+
+/// This PE and its neighbours when all PEs form a ring, each sending to the next one
+pub struct Ring {
+    /// the runtime, finalized when the ring is dropped
+    pub shmem: Shmem,
+    pub me: i32,
+    /// the PE this one sends to
+    pub target: i32,
+    /// the PE that sends to this one
+    pub from: i32,
+}
+
+impl Ring {
+    /// initializes the runtime
+    pub fn init() -> Ring {
+        let shmem = Shmem::init().unwrap();
+        let me = shmem.my_pe();
+        let n_pes = shmem.n_pes();
+        Ring {
+            shmem,
+            me,
+            target: (me + 1) % n_pes,
+            from: (me + n_pes - 1) % n_pes,
+        }
+    }
+}
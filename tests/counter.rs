@@ -0,0 +1,24 @@
+//! Hands out work items from a counter on PE 0.
+
+#![cfg(all(feature = "runtime-tests", shmem_1_4))]
+
+use openshmem_sys::Shmem;
+use openshmem_sys::counter::DistributedCounter;
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn fetch_inc_hands_out_each_value_once() {
+    let shmem = Shmem::init().unwrap();
+    let counter = DistributedCounter::new(0).unwrap();
+    assert_eq!(counter.owner(), 0);
+
+    let taken: Vec<i64> = (0..3).map(|_| counter.fetch_inc()).collect();
+    assert!(
+        taken.windows(2).all(|pair| pair[0] < pair[1]),
+        "{:?}",
+        taken
+    );
+    barrier_all();
+    assert_eq!(counter.read(), 3 * i64::from(shmem.n_pes()));
+    barrier_all();
+}
@@ -0,0 +1,14 @@
+//! Checks that `SHMEM_SYMMETRIC_SIZE` is parsed the way the libraries read it.
+//!
+//! The size is only read once per process, so this file holds a single test.
+
+use openshmem_sys::mem::symmetric_heap_size;
+
+#[test]
+fn suffixed_heap_size() {
+    unsafe { std::env::set_var("SHMEM_SYMMETRIC_SIZE", "1.5G") };
+    assert_eq!(symmetric_heap_size(), Some(3 << 29));
+    // Later changes are not seen, neither are they by the library.
+    unsafe { std::env::set_var("SHMEM_SYMMETRIC_SIZE", "64M") };
+    assert_eq!(symmetric_heap_size(), Some(3 << 29));
+}
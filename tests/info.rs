@@ -0,0 +1,22 @@
+//! Checks the implementation name and version queries.
+//!
+//! These need the OpenSHMEM library, but not an initialized runtime.
+
+#![cfg(feature = "runtime-tests")]
+
+use openshmem_sys::{info, limits};
+
+#[test]
+fn name_and_version() {
+    let name = info::name();
+    assert!(!name.is_empty(), "the implementation has no name");
+    assert!(!name.contains('\0'));
+    let (major, minor) = info::version();
+    assert!(major >= 1 && minor >= 0, "version {}.{}", major, minor);
+}
+
+#[test]
+fn name_fits_max_name_len() {
+    // The limit includes the terminating NUL.
+    assert!(info::name().len() < limits::SHMEM_MAX_NAME_LEN);
+}
@@ -0,0 +1,33 @@
+//! Checks that a peer's symmetric array can be read directly when `shmem_ptr` allows it.
+
+#![cfg(feature = "runtime-tests")]
+
+mod common;
+
+use common::Ring;
+use openshmem_sys::accessibility::local_peer_slice;
+use openshmem_sys::mem::SymmetricVec;
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn read_peer_through_shmem_ptr() {
+    let ring = Ring::init();
+
+    let mut data = SymmetricVec::from_elem(0i32, 8, 1).unwrap();
+    for (i, x) in data.iter_mut().enumerate() {
+        *x = ring.me * 10 + i as i32;
+    }
+    barrier_all();
+
+    // Nobody writes `data` between the two barriers, and every PE frees it only afterwards.
+    // The peer may be on another node, then there is nothing to check.
+    if let Some(view) = unsafe { local_peer_slice(&data, ring.target) } {
+        let expected: Vec<i32> = (0..8).map(|i| ring.target * 10 + i).collect();
+        assert_eq!(view, &expected[..]);
+    }
+    assert_eq!(
+        unsafe { local_peer_slice(&data, ring.me) }.unwrap(),
+        &data[..]
+    );
+    barrier_all();
+}
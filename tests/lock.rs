@@ -0,0 +1,36 @@
+//! Checks that a distributed lock serializes updates from all PEs.
+//!
+//! With a single PE there is no contention, run it with at least two.
+
+#![cfg(feature = "runtime-tests")]
+
+use openshmem_sys::Shmem;
+use openshmem_sys::lock::DistributedLock;
+use openshmem_sys::mem::SymmetricVec;
+use openshmem_sys::rma::{get, put};
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn lock_serializes_read_modify_write() {
+    let shmem = Shmem::init().unwrap();
+    let lock = DistributedLock::new().unwrap();
+    let mut counter = SymmetricVec::from_elem(0i64, 1, 1).unwrap();
+
+    // An unprotected get + put on PE 0 would lose increments.
+    for _ in 0..10 {
+        let _guard = lock.guard();
+        let mut value = [0i64];
+        get(&mut value, &counter, 0);
+        value[0] += 1;
+        put(&mut counter, &value, 0);
+    }
+    barrier_all();
+
+    if shmem.my_pe() == 0 {
+        assert_eq!(counter[0], 10 * shmem.n_pes() as i64);
+        assert!(lock.try_lock());
+        lock.unlock();
+        assert!(lock.try_guard().is_some());
+    }
+    barrier_all();
+}
@@ -0,0 +1,34 @@
+//! Checks that a batch of non-blocking transfers is visible once it is completed.
+
+#![cfg(feature = "runtime-tests")]
+
+mod common;
+
+use common::Ring;
+use openshmem_sys::mem::SymmetricVec;
+use openshmem_sys::rma::nonblocking::nbi_scope;
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn batch_complete() {
+    let ring = Ring::init();
+
+    let mut dest = SymmetricVec::from_elem(0i32, 8, 1).unwrap();
+    let src: Vec<i32> = (0..8).map(|i| ring.me * 10 + i).collect();
+    barrier_all();
+
+    let (low, high) = dest.split_at_mut(4);
+    let outstanding = nbi_scope(|batch| {
+        batch.put(low, &src[..4], ring.target);
+        batch.put(high, &src[4..], ring.target);
+        let outstanding = batch.outstanding();
+        batch.complete();
+        assert_eq!(batch.outstanding(), 0);
+        outstanding
+    });
+    assert_eq!(outstanding, 2);
+    barrier_all();
+
+    let expected: Vec<i32> = (0..8).map(|i| ring.from * 10 + i).collect();
+    assert_eq!(*dest, expected[..]);
+}
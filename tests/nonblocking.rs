@@ -0,0 +1,28 @@
+//! Checks that a non-blocking put is visible after `quiet`.
+
+#![cfg(feature = "runtime-tests")]
+
+mod common;
+
+use common::Ring;
+use openshmem_sys::mem::SymmetricBox;
+use openshmem_sys::rma::nonblocking::put_nbi;
+use openshmem_sys::sync::{barrier_all, quiet};
+
+#[test]
+fn put_nbi_then_quiet() {
+    let ring = Ring::init();
+
+    let mut dest = SymmetricBox::new([0i32; 4]).unwrap();
+    let src = [ring.me, ring.me + 1, ring.me + 2, ring.me + 3];
+    barrier_all();
+    // Neither buffer is touched before the quiet.
+    unsafe { put_nbi(&mut dest[..], &src, ring.target) };
+    quiet();
+    barrier_all();
+
+    assert_eq!(
+        *dest,
+        [ring.from, ring.from + 1, ring.from + 2, ring.from + 3]
+    );
+}
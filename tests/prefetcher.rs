@@ -0,0 +1,37 @@
+//! Checks that a prefetcher gathers blocks from other PEs into one buffer.
+
+#![cfg(feature = "runtime-tests")]
+
+use openshmem_sys::Shmem;
+use openshmem_sys::mem::SymmetricVec;
+use openshmem_sys::rma::nonblocking::prefetch;
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn gather_from_all_pes() {
+    let shmem = Shmem::init().unwrap();
+    let n_pes = shmem.n_pes();
+
+    let mut src = SymmetricVec::from_elem(0i32, 4, 1).unwrap();
+    for (i, x) in src.iter_mut().enumerate() {
+        *x = shmem.my_pe() * 10 + i as i32;
+    }
+    barrier_all();
+
+    // The second half of every PE's array, in PE order.
+    let mut buffer = vec![-1; 2 * n_pes as usize];
+    let gathered = prefetch(&mut buffer, |prefetcher| {
+        for pe in 0..n_pes {
+            prefetcher.fetch(2 * pe as usize, &src[2..], pe);
+        }
+        assert_eq!(prefetcher.outstanding(), n_pes as usize);
+        prefetcher.wait();
+        assert_eq!(prefetcher.outstanding(), 0);
+    });
+
+    let expected: Vec<i32> = (0..n_pes)
+        .flat_map(|pe| [pe * 10 + 2, pe * 10 + 3])
+        .collect();
+    assert_eq!(gathered, &expected[..]);
+    barrier_all();
+}
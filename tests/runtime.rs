@@ -0,0 +1,21 @@
+//! Checks that the bindings link against and run with the installed library.
+//!
+//! These need a working OpenSHMEM runtime, run them with something like
+//! `CARGO_TARGET_X86_64_UNKNOWN_LINUX_GNU_RUNNER="oshrun -n 1" cargo test --features runtime-tests`.
+//! OpenSHMEM can only be initialized once per process, so everything lives in a single test.
+
+#![cfg(feature = "runtime-tests")]
+
+use openshmem_sys::{shmem_finalize, shmem_init, shmem_my_pe, shmem_n_pes};
+
+#[test]
+fn init_query_finalize() {
+    unsafe {
+        shmem_init();
+        let my_pe = shmem_my_pe();
+        let n_pes = shmem_n_pes();
+        assert!(my_pe >= 0, "shmem_my_pe returned {}", my_pe);
+        assert!(my_pe < n_pes, "PE {} out of {} PEs", my_pe, n_pes);
+        shmem_finalize();
+    }
+}
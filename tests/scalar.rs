@@ -0,0 +1,27 @@
+//! Checks single element puts and gets.
+
+#![cfg(feature = "runtime-tests")]
+
+mod common;
+
+use common::Ring;
+use openshmem_sys::mem::SymmetricBox;
+use openshmem_sys::rma::scalar::{get_scalar, put_scalar};
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn put_and_get_scalars() {
+    let ring = Ring::init();
+
+    let mut flag = SymmetricBox::new(0i64).unwrap();
+    let mut value = SymmetricBox::new(0.0f64).unwrap();
+    barrier_all();
+    put_scalar(&mut *value, f64::from(ring.me) + 0.5, ring.target);
+    put_scalar(&mut *flag, 1 + ring.me as i64, ring.target);
+    barrier_all();
+    assert_eq!(*flag, 1 + ring.from as i64);
+    assert_eq!(*value, f64::from(ring.from) + 0.5);
+
+    assert_eq!(get_scalar(&*flag, ring.target), 1 + ring.me as i64);
+    barrier_all();
+}
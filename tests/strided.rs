@@ -0,0 +1,31 @@
+//! Checks that strided puts and gets touch exactly the elements they should.
+
+#![cfg(feature = "runtime-tests")]
+
+mod common;
+
+use common::Ring;
+use openshmem_sys::mem::SymmetricBox;
+use openshmem_sys::rma::strided::{iget, iput};
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn iput_and_iget_strides() {
+    let ring = Ring::init();
+
+    // Every other element of `src` goes to every third element of the target's `dest`.
+    let mut dest = SymmetricBox::new([-1i64; 7]).unwrap();
+    let src = [10, 11, 12, 13, 14].map(|v| v + 100 * i64::from(ring.me));
+    barrier_all();
+    iput(&mut dest[..], &src, 3, 2, 3, ring.target);
+    barrier_all();
+    let base = 100 * i64::from(ring.from);
+    assert_eq!(*dest, [base + 10, -1, -1, base + 12, -1, -1, base + 14]);
+
+    // And back: every third element of the target's `dest` into consecutive elements.
+    let mut back = [0i64; 3];
+    iget(&mut back, &dest[..], 1, 3, 3, ring.target);
+    let base = 100 * i64::from(ring.me);
+    assert_eq!(back, [base + 10, base + 12, base + 14]);
+    barrier_all();
+}
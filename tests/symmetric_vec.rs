@@ -0,0 +1,29 @@
+//! Checks that symmetric arrays are aligned as requested and usable as put targets.
+
+#![cfg(feature = "runtime-tests")]
+
+mod common;
+
+use common::Ring;
+use openshmem_sys::mem::SymmetricVec;
+use openshmem_sys::rma::put;
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn aligned_and_zeroed_arrays() {
+    let ring = Ring::init();
+
+    let mut dest = SymmetricVec::from_elem(-1i32, 16, 64).unwrap();
+    assert_eq!(dest.as_ptr() as usize % 64, 0);
+    assert_eq!(*dest, [-1; 16]);
+    let zeroed = unsafe { SymmetricVec::<f64>::zeroed(8) }.unwrap();
+    assert_eq!(*zeroed, [0.0; 8]);
+
+    let src: Vec<i32> = (0..16).map(|i| ring.me * 100 + i).collect();
+    barrier_all();
+    put(&mut dest[..], &src, ring.target);
+    barrier_all();
+
+    let expected: Vec<i32> = (0..16).map(|i| ring.from * 100 + i).collect();
+    assert_eq!(*dest, expected[..]);
+}
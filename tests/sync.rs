@@ -0,0 +1,27 @@
+//! Publishes data before a flag with `order_puts`, then completes both with `complete_puts`.
+
+#![cfg(feature = "runtime-tests")]
+
+mod common;
+
+use common::Ring;
+use openshmem_sys::mem::SymmetricBox;
+use openshmem_sys::rma::put;
+use openshmem_sys::sync::{barrier_all, complete_puts, order_puts};
+
+#[test]
+fn order_then_complete_puts() {
+    let ring = Ring::init();
+
+    let mut data = SymmetricBox::new([0i32; 4]).unwrap();
+    let mut flag = SymmetricBox::new([0i32; 1]).unwrap();
+    barrier_all();
+    put(&mut data[..], &[ring.me; 4], ring.target);
+    order_puts();
+    put(&mut flag[..], &[1], ring.target);
+    complete_puts();
+    barrier_all();
+
+    assert_eq!(*flag, [1]);
+    assert_eq!(*data, [ring.from; 4]);
+}
@@ -0,0 +1,16 @@
+//! Checks at compile time that sizes and counts in the bindings are `usize`, so slice lengths can
+//! be passed straight through.
+
+use std::os::raw::{c_int, c_void};
+
+use openshmem_sys::{ptrdiff_t, shmem_getmem, shmem_malloc, shmem_putmem};
+
+type Rma = unsafe extern "C" fn(*mut c_void, *const c_void, usize, c_int);
+
+#[test]
+fn sizes_are_usize() {
+    let _: Rma = shmem_putmem;
+    let _: Rma = shmem_getmem;
+    let _: unsafe extern "C" fn(usize) -> *mut c_void = shmem_malloc;
+    let _: isize = 0 as ptrdiff_t;
+}
@@ -0,0 +1,37 @@
+//! Checks waiting on several symmetric variables at once.
+//!
+//! The build script's `shmem_1_5` cfg reaches the tests of this package too, so against an
+//! older library than 1.5 the file compiles to nothing, like the wrappers it calls.
+
+#![cfg(all(feature = "runtime-tests", feature = "shmem-1-5", shmem_1_5))]
+
+mod common;
+
+use common::Ring;
+use openshmem_sys::mem::SymmetricBox;
+use openshmem_sys::rma::put;
+use openshmem_sys::signal::{Cmp, wait_until_all, wait_until_any, wait_until_some};
+use openshmem_sys::sync::barrier_all;
+
+#[test]
+fn wait_on_several_vars() {
+    let ring = Ring::init();
+
+    let mut flags = SymmetricBox::new([0i64; 4]).unwrap();
+    barrier_all();
+    put(&mut flags[1..3], &[1, 1], ring.target);
+
+    let skip_others = [true, false, false, true];
+    wait_until_all(&mut flags[..], Cmp::Eq, 1, Some(&skip_others));
+    let any = wait_until_any(&mut flags[..], Cmp::Eq, 1, None);
+    assert!(matches!(any, Some(1 | 2)), "{:?}", any);
+    let mut some = wait_until_some(&mut flags[..], Cmp::Eq, 1, None);
+    some.sort_unstable();
+    assert_eq!(some, [1, 2]);
+    assert_eq!(
+        wait_until_any(&mut flags[..], Cmp::Eq, 1, Some(&[true; 4])),
+        None
+    );
+    assert!(wait_until_some(&mut flags[..], Cmp::Eq, 1, Some(&[true; 4])).is_empty());
+    barrier_all();
+}